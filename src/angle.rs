@@ -0,0 +1,110 @@
+//! Angles, in whichever units are easiest
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::core::T;
+
+/// An angle, anticlockwise from the positive x-axis - stored in "turns" as the rest of the crate measures angles, but
+/// it can be made from and turned into radians or degrees too. Unlike [`T`] it isn't clamped, so it can go the other
+/// way or round more than once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Angle(f32);
+
+impl Angle {
+    /// the angle of `turns` turns, so a quarter turn is `0.25`
+    pub fn turns(turns: f32) -> Self {
+        Self(turns)
+    }
+
+    /// the angle of `radians` radians, so a quarter turn is `PI / 2`
+    pub fn radians(radians: f32) -> Self {
+        Self(radians / std::f32::consts::TAU)
+    }
+
+    /// the angle of `degrees` degrees, so a quarter turn is `90.0`
+    pub fn degrees(degrees: f32) -> Self {
+        Self(degrees / 360.0)
+    }
+
+    /// returns the angle in turns
+    pub fn to_turns(&self) -> f32 {
+        self.0
+    }
+
+    /// returns the angle in radians
+    pub fn to_radians(&self) -> f32 {
+        self.0 * std::f32::consts::TAU
+    }
+
+    /// returns the angle in degrees
+    pub fn to_degrees(&self) -> f32 {
+        self.0 * 360.0
+    }
+
+    /// returns the sine and cosine of the angle
+    pub fn sin_cos(&self) -> (f32, f32) {
+        self.to_radians().sin_cos()
+    }
+}
+
+/// a [`T`] taken as a number of turns, which is how angles were given before [`Angle`]
+impl From<T> for Angle {
+    fn from(t: T) -> Self {
+        Self(t.value())
+    }
+}
+
+impl Add for Angle {
+    type Output = Angle;
+
+    fn add(self, other: Angle) -> Angle {
+        Angle(self.0 + other.0)
+    }
+}
+
+impl Sub for Angle {
+    type Output = Angle;
+
+    fn sub(self, other: Angle) -> Angle {
+        Angle(self.0 - other.0)
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+
+    fn neg(self) -> Angle {
+        Angle(-self.0)
+    }
+}
+
+impl Mul<f32> for Angle {
+    type Output = Angle;
+
+    fn mul(self, scale: f32) -> Angle {
+        Angle(self.0 * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_angle() {
+        let right = Angle::degrees(90.0);
+        assert_relative_eq!(right.to_turns(), 0.25);
+        assert_relative_eq!(right.to_radians(), std::f32::consts::FRAC_PI_2);
+        assert_eq!(Angle::radians(std::f32::consts::PI), Angle::turns(0.5));
+        assert_eq!(Angle::from(T::new(0.25)), right);
+
+        assert_relative_eq!((right + right).to_degrees(), 180.0);
+        assert_relative_eq!((-right * 3.0).to_degrees(), -270.0);
+        assert_relative_eq!((right - Angle::turns(1.0)).to_turns(), -0.75);
+
+        let (sin, cos) = right.sin_cos();
+        assert_relative_eq!(sin, 1.0);
+        assert_relative_eq!(cos, 0.0, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,227 @@
+//! [`proptest`](mod@proptest) strategies and [`Arbitrary`] implementations, enabled by the `proptest` feature
+
+use std::{fmt, ops::RangeInclusive, rc::Rc};
+
+use proptest::prelude::*;
+
+use crate::{
+    bezier::{BezierFourth, BezierSecond, BezierThird},
+    circle::{Circle, CircleArc},
+    core::{
+        Concat, IntoParametric2D, ParametricFunction2D, Point, Repeat, Rotate, Scale, Translate, T,
+    },
+    segment::Segment,
+};
+
+/// Range from which generated coordinates are drawn
+const COORDINATES: RangeInclusive<f32> = -100.0..=100.0;
+
+/// Range from which generated radii and scale factors are drawn
+const SIZES: RangeInclusive<f32> = 0.01..=100.0;
+
+/// Depth of the combinator trees generated by [`ArbitraryCurve`]'s [`Arbitrary`] implementation
+const DEFAULT_DEPTH: u32 = 3;
+
+/// Strategy for points with both coordinates in `-100.0..=100.0`
+pub fn point() -> impl Strategy<Value = Point> {
+    (COORDINATES, COORDINATES).prop_map(|(x, y)| Point::new(x, y))
+}
+
+impl Arbitrary for T {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (0.0_f32..=1.0).prop_map(T::new).boxed()
+    }
+}
+
+impl Arbitrary for Segment {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (point(), point())
+            .prop_map(|(start, end)| Segment::new(start, end))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Circle {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (point(), SIZES, any::<T>())
+            .prop_map(|(centre, radius, start_angle)| {
+                Circle::new(centre, radius, Some(start_angle))
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CircleArc {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (point(), SIZES, any::<T>(), any::<T>())
+            .prop_map(|(centre, radius, start_angle, end_angle)| {
+                CircleArc::new(centre, radius, Some(start_angle), Some(end_angle))
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for BezierSecond {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (point(), point(), point())
+            .prop_map(|(start, end, control)| BezierSecond::new(start, end, control))
+            .boxed()
+    }
+}
+
+impl Arbitrary for BezierThird {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (point(), point(), point(), point())
+            .prop_map(|(start, end, control1, control2)| {
+                BezierThird::new(start, end, control1, control2)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for BezierFourth {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (point(), point(), point(), point(), point())
+            .prop_map(|(start, end, control1, control2, control3)| {
+                BezierFourth::new(start, end, control1, control2, control3)
+            })
+            .boxed()
+    }
+}
+
+/// A generated curve, either a primitive or a tree of combinators over primitives, along with a description of
+/// how it was built (which is what gets printed when a property fails)
+#[derive(Clone)]
+pub struct ArbitraryCurve {
+    pub description: String,
+    pub curve: Rc<Box<dyn ParametricFunction2D>>,
+}
+
+impl ArbitraryCurve {
+    fn new(description: String, curve: impl IntoParametric2D) -> Self {
+        Self {
+            description,
+            curve: curve.into_parametric(),
+        }
+    }
+}
+
+impl fmt::Debug for ArbitraryCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+impl ParametricFunction2D for ArbitraryCurve {
+    fn evaluate(&self, t: T) -> Point {
+        self.curve.evaluate(t)
+    }
+
+    fn describe(&self) -> String {
+        self.curve.describe()
+    }
+}
+
+/// Strategy for any of the primitive curves
+pub fn primitive() -> BoxedStrategy<ArbitraryCurve> {
+    prop_oneof![
+        any::<Segment>().prop_map(|c| ArbitraryCurve::new(format!("{:?}", c), c)),
+        any::<Circle>().prop_map(|c| ArbitraryCurve::new(format!("{:?}", c), c)),
+        any::<CircleArc>().prop_map(|c| ArbitraryCurve::new(format!("{:?}", c), c)),
+        any::<BezierSecond>().prop_map(|c| ArbitraryCurve::new(format!("{:?}", c), c)),
+        any::<BezierThird>().prop_map(|c| ArbitraryCurve::new(format!("{:?}", c), c)),
+        any::<BezierFourth>().prop_map(|c| ArbitraryCurve::new(format!("{:?}", c), c)),
+    ]
+    .boxed()
+}
+
+/// Strategy for trees of combinators, at most `depth` deep, with primitive curves at the leaves
+pub fn curve_tree(depth: u32) -> BoxedStrategy<ArbitraryCurve> {
+    primitive()
+        .prop_recursive(depth, 64, 4, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 1..4).prop_map(|children| {
+                    let description = format!("Concat{:?}", children);
+                    let functions = children.into_iter().map(|c| c.curve).collect();
+                    ArbitraryCurve::new(description, Concat { functions })
+                }),
+                (inner.clone(), 1_usize..4).prop_map(|(c, n)| {
+                    ArbitraryCurve::new(format!("Repeat({:?}, {})", c, n), Repeat::new(c.curve, n))
+                }),
+                (inner.clone(), point(), any::<T>()).prop_map(|(c, centre, angle)| {
+                    ArbitraryCurve::new(
+                        format!("Rotate({:?}, {:?}, {:?})", c, centre, angle),
+                        Rotate::new(c.curve, centre, angle),
+                    )
+                }),
+                (inner.clone(), point()).prop_map(|(c, by)| {
+                    ArbitraryCurve::new(
+                        format!("Translate({:?}, {:?})", c, by),
+                        Translate::new(c.curve, by),
+                    )
+                }),
+                (inner, point(), SIZES, SIZES).prop_map(|(c, centre, scale_x, scale_y)| {
+                    ArbitraryCurve::new(
+                        format!("Scale({:?}, {:?}, {}, {})", c, centre, scale_x, scale_y),
+                        Scale::new(c.curve, centre, scale_x, scale_y),
+                    )
+                }),
+            ]
+        })
+        .boxed()
+}
+
+impl Arbitrary for ArbitraryCurve {
+    /// the maximum depth of the combinator tree
+    type Parameters = Option<u32>;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(depth: Self::Parameters) -> Self::Strategy {
+        curve_tree(depth.unwrap_or(DEFAULT_DEPTH))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_t_in_range(t in any::<T>()) {
+            prop_assert!((0.0..=1.0).contains(&t.value()));
+        }
+
+        #[test]
+        fn test_segment_endpoints(s in any::<Segment>()) {
+            prop_assert!((s.start() - s.start).length() < 1e-3);
+            prop_assert!((s.end() - s.end).length() < 1e-3);
+        }
+
+        #[test]
+        fn test_curve_tree_is_finite(c in any::<ArbitraryCurve>(), t in any::<T>()) {
+            let p = c.evaluate(t);
+            prop_assert!(p.x.is_finite() && p.y.is_finite());
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Parameterisation by arc length
+
+use std::rc::Rc;
+
+use crate::core::{IntoParametric2D, ParametricFunction2D, Point, T};
+
+/// Number of intervals the arc length is tabulated over
+const TABLE_SIZE: usize = 256;
+
+/// A table of the arc length of a parametric function against `t`, for converting distances along it back into
+/// values of `t`
+#[derive(Clone)]
+pub struct ArcLengthTable {
+    lengths: Vec<f32>,
+}
+
+impl ArcLengthTable {
+    /// tabulates the arc length of `function` over equally spaced values of `t`, each interval being measured with
+    /// [`ParametricFunction2D::arc_length_between`] so that the total is accurate to roughly `tolerance`
+    pub fn new<F: ParametricFunction2D + ?Sized>(function: &F, tolerance: f32) -> Self {
+        let step_size = 1.0 / TABLE_SIZE as f32;
+        let mut lengths = Vec::with_capacity(TABLE_SIZE + 1);
+        lengths.push(0.0);
+
+        let mut total = 0.0;
+        for i in 0..TABLE_SIZE {
+            let a = T::new(i as f32 * step_size);
+            let b = T::new((i + 1) as f32 * step_size);
+            total += function.arc_length_between(a, b, tolerance / TABLE_SIZE as f32);
+            lengths.push(total);
+        }
+
+        Self { lengths }
+    }
+
+    /// returns the total arc length
+    pub fn total(&self) -> f32 {
+        self.lengths[TABLE_SIZE]
+    }
+
+    /// returns the arc length from the start up to `t`
+    pub fn length_at(&self, t: T) -> f32 {
+        let position = t.value() * TABLE_SIZE as f32;
+        let index = (position.floor() as usize).min(TABLE_SIZE - 1);
+        let fraction = position - index as f32;
+        self.lengths[index] + (self.lengths[index + 1] - self.lengths[index]) * fraction
+    }
+
+    /// returns the `t` at which the arc length from the start is `length` - lengths beyond either end are clamped
+    pub fn t_at_length(&self, length: f32) -> T {
+        if length <= 0.0 {
+            return T::start();
+        }
+        if length >= self.total() {
+            return T::end();
+        }
+
+        let index = self.lengths.partition_point(|&l| l <= length) - 1;
+        let (a, b) = (self.lengths[index], self.lengths[index + 1]);
+        let fraction = if b > a { (length - a) / (b - a) } else { 0.0 };
+        T::new((index as f32 + fraction) / TABLE_SIZE as f32)
+    }
+
+    /// returns the `t` at which the given `fraction` of the total arc length has been covered
+    pub fn t_at_fraction(&self, fraction: T) -> T {
+        self.t_at_length(fraction.value() * self.total())
+    }
+}
+
+/// How far apart [`ParametricFunction2D::stamps_along`] spaces its stamps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Spacing {
+    /// this many stamps, one at each end and the rest evenly spaced in between
+    Count(usize),
+    /// a stamp at the start and then every this far along - a distance if going by arc length, and otherwise a step
+    /// in `t`
+    Every(f32),
+}
+
+/// A thing that implements [`ParametricFunction2D`] reparameterised by arc length, so that it is traversed at a
+/// constant speed - e.g. `t = 0.5` is always halfway along it by distance
+#[derive(Clone)]
+pub struct ConstantSpeed {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    table: ArcLengthTable,
+}
+
+impl ConstantSpeed {
+    /// see [`ArcLengthTable::new`] for the meaning of `tolerance`
+    pub fn new(function: impl IntoParametric2D, tolerance: f32) -> Self {
+        let function = function.into_parametric();
+        let table = ArcLengthTable::new(function.as_ref().as_ref(), tolerance);
+        Self { function, table }
+    }
+
+    /// returns the table of arc lengths the reparameterisation uses
+    pub fn table(&self) -> &ArcLengthTable {
+        &self.table
+    }
+}
+
+impl ParametricFunction2D for ConstantSpeed {
+    fn evaluate(&self, t: T) -> Point {
+        self.function.evaluate(self.table.t_at_fraction(t))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        // the optimized function traces the same points, so the table still holds
+        Some(Rc::new(Box::new(ConstantSpeed {
+            function: self.function.optimize()?,
+            table: self.table.clone(),
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("ConstantSpeed({})", self.function.describe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bezier::BezierSecond, segment::Segment};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_arc_length_table() {
+        let s = Segment::new((0.0, 0.0).into(), (3.0, 4.0).into());
+        let table = ArcLengthTable::new(&s, 1e-4);
+
+        assert_relative_eq!(table.total(), 5.0, epsilon = 1e-4);
+        assert_relative_eq!(table.length_at(T::new(0.3)), 1.5, epsilon = 1e-4);
+        assert_relative_eq!(table.t_at_length(2.5).value(), 0.5, epsilon = 1e-4);
+        assert_relative_eq!(table.t_at_length(10.0).value(), 1.0);
+        assert_relative_eq!(
+            table.t_at_fraction(T::new(0.2)).value(),
+            0.2,
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn test_constant_speed() {
+        // the control point bunches the parameterisation up towards the end
+        let b = BezierSecond::new((0.0, 0.0).into(), (4.0, 0.0).into(), (4.0, 0.0).into());
+        let res = b.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 3.0);
+
+        let c = ConstantSpeed::new(b, 1e-4);
+        let res = c.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 2.0, epsilon = 1e-3);
+
+        let res = c.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-3);
+
+        let res = c.end();
+        assert_relative_eq!(res.x, 4.0);
+    }
+}
@@ -1,10 +1,18 @@
 //! Bezier curves
 
-use std::rc::Rc;
-
-use crate::{core::Concat, core::ParametricFunction2D, core::Point, core::T, segment::Segment};
+use crate::{
+    continuity::{continuity_breaks, ContinuityBreak},
+    core::locate,
+    core::ParametricFunction2D,
+    core::Point,
+    core::Vector,
+    core::T,
+    error::{check_points, ParametricsError},
+    segment::Segment,
+};
 
 /// Second Order Bezier curve
+#[derive(Debug, Clone, PartialEq)]
 pub struct BezierSecond {
     pub start: Point,
     pub end: Point,
@@ -19,6 +27,27 @@ impl BezierSecond {
             control,
         }
     }
+
+    /// as [`BezierSecond::new`] but fails if any point isn't finite
+    pub fn try_new(start: Point, end: Point, control: Point) -> Result<Self, ParametricsError> {
+        check_points(&[start, end, control])?;
+        Ok(Self::new(start, end, control))
+    }
+
+    /// splits the curve at `t` into the two curves either side, see [`BezierNth::subdivide`]
+    pub fn subdivide(&self, t: T) -> (Self, Self) {
+        let (a, b) = BezierNth::new([self.start, self.control, self.end]).subdivide(t);
+        (a.into(), b.into())
+    }
+
+    /// returns the derivative with respect to `t` as a curve of its own (the hodograph) - for a quadratic this is a
+    /// straight line, with the points on it standing for vectors
+    pub fn derivative_curve(&self) -> Segment {
+        Segment::new(
+            ((self.control - self.start) * 2.0).to_point(),
+            ((self.end - self.control) * 2.0).to_point(),
+        )
+    }
 }
 
 impl ParametricFunction2D for BezierSecond {
@@ -42,9 +71,14 @@ impl ParametricFunction2D for BezierSecond {
         let s = Segment { start: t1, end: t2 };
         s.evaluate(t)
     }
+
+    fn derivative(&self, t: T) -> Vector {
+        self.derivative_curve().evaluate(t).to_vector()
+    }
 }
 
 /// Third Order Bezier curve
+#[derive(Debug, Clone, PartialEq)]
 pub struct BezierThird {
     pub start: Point,
     pub end: Point,
@@ -61,6 +95,34 @@ impl BezierThird {
             control2,
         }
     }
+
+    /// as [`BezierThird::new`] but fails if any point isn't finite
+    pub fn try_new(
+        start: Point,
+        end: Point,
+        control1: Point,
+        control2: Point,
+    ) -> Result<Self, ParametricsError> {
+        check_points(&[start, end, control1, control2])?;
+        Ok(Self::new(start, end, control1, control2))
+    }
+
+    /// splits the curve at `t` into the two curves either side, see [`BezierNth::subdivide`]
+    pub fn subdivide(&self, t: T) -> (Self, Self) {
+        let (a, b) =
+            BezierNth::new([self.start, self.control1, self.control2, self.end]).subdivide(t);
+        (a.into(), b.into())
+    }
+
+    /// returns the derivative with respect to `t` as a curve of its own (the hodograph), with the points on it
+    /// standing for vectors
+    pub fn derivative_curve(&self) -> BezierSecond {
+        BezierSecond::new(
+            ((self.control1 - self.start) * 3.0).to_point(),
+            ((self.end - self.control2) * 3.0).to_point(),
+            ((self.control2 - self.control1) * 3.0).to_point(),
+        )
+    }
 }
 
 impl ParametricFunction2D for BezierThird {
@@ -95,9 +157,14 @@ impl ParametricFunction2D for BezierThird {
 
         b.evaluate(t)
     }
+
+    fn derivative(&self, t: T) -> Vector {
+        self.derivative_curve().evaluate(t).to_vector()
+    }
 }
 
 /// Fourth Order Bezier curve
+#[derive(Debug, Clone, PartialEq)]
 pub struct BezierFourth {
     pub start: Point,
     pub end: Point,
@@ -122,6 +189,42 @@ impl BezierFourth {
             control3,
         }
     }
+
+    /// as [`BezierFourth::new`] but fails if any point isn't finite
+    pub fn try_new(
+        start: Point,
+        end: Point,
+        control1: Point,
+        control2: Point,
+        control3: Point,
+    ) -> Result<Self, ParametricsError> {
+        check_points(&[start, end, control1, control2, control3])?;
+        Ok(Self::new(start, end, control1, control2, control3))
+    }
+
+    /// splits the curve at `t` into the two curves either side, see [`BezierNth::subdivide`]
+    pub fn subdivide(&self, t: T) -> (Self, Self) {
+        let (a, b) = BezierNth::new([
+            self.start,
+            self.control1,
+            self.control2,
+            self.control3,
+            self.end,
+        ])
+        .subdivide(t);
+        (a.into(), b.into())
+    }
+
+    /// returns the derivative with respect to `t` as a curve of its own (the hodograph), with the points on it
+    /// standing for vectors
+    pub fn derivative_curve(&self) -> BezierThird {
+        BezierThird::new(
+            ((self.control1 - self.start) * 4.0).to_point(),
+            ((self.end - self.control3) * 4.0).to_point(),
+            ((self.control2 - self.control1) * 4.0).to_point(),
+            ((self.control3 - self.control2) * 4.0).to_point(),
+        )
+    }
 }
 
 impl ParametricFunction2D for BezierFourth {
@@ -163,135 +266,430 @@ impl ParametricFunction2D for BezierFourth {
 
         b.evaluate(t)
     }
+
+    fn derivative(&self, t: T) -> Vector {
+        self.derivative_curve().evaluate(t).to_vector()
+    }
 }
 
-/// Second Order Bezier spline
+/// Second Order Bezier spline - if `closed` is set, the last control point in `points` lead back round to the first
+/// point, closing up the shape
+#[derive(Debug, Clone, PartialEq)]
 pub struct BezierSecondSpline {
     pub points: Vec<Point>,
+    pub closed: bool,
 }
 
 impl BezierSecondSpline {
     pub fn new(points: Vec<Point>) -> Self {
-        Self { points }
+        Self {
+            points,
+            closed: false,
+        }
+    }
+
+    /// the spline closed back round to the first point
+    pub fn closed(points: Vec<Point>) -> Self {
+        Self {
+            points,
+            closed: true,
+        }
+    }
+
+    /// returns the number of pieces in the spline
+    fn count(&self) -> usize {
+        (self.points.len() + self.closed as usize).saturating_sub(1) / 2
+    }
+
+    /// returns piece `i` of the spline
+    fn segment(&self, i: usize) -> BezierSecond {
+        let p = |k: usize| self.points[(i * 2 + k) % self.points.len()];
+        BezierSecond::new(p(0), p(2), p(1))
+    }
+
+    /// returns the pieces of the spline in order
+    pub fn segments(&self) -> impl Iterator<Item = BezierSecond> + '_ {
+        (0..self.count()).map(|i| self.segment(i))
+    }
+
+    /// returns the joins between the pieces that aren't smooth to within `tolerance`, see [`ContinuityBreak`]
+    pub fn analyze_continuity(&self, tolerance: f32) -> Vec<ContinuityBreak> {
+        let segments: Vec<_> = self.segments().collect();
+        continuity_breaks(
+            segments.iter().map(|s| s as &dyn ParametricFunction2D),
+            self.closed,
+            tolerance,
+        )
     }
 }
 
 impl ParametricFunction2D for BezierSecondSpline {
     fn evaluate(&self, t: T) -> Point {
-        let step = 2;
-        let bs: Vec<_> = self
-            .points
-            .windows(3)
-            .enumerate()
-            .filter(|&(i, _)| i % step == 0)
-            .map(|(_, t)| {
-                let t = t.to_vec();
-                Rc::new(Box::new(BezierSecond {
-                    start: t[0],
-                    end: t[2],
-                    control: t[1],
-                }) as Box<dyn ParametricFunction2D>)
-            })
-            .collect();
-
-        let concat = Concat { functions: bs };
-        concat.evaluate(t)
+        match locate(self.count(), t) {
+            Some((i, t)) => self.segment(i).evaluate(t),
+            None => self.points.first().copied().unwrap_or_else(Point::origin),
+        }
     }
 }
 
-/// Third Order Bezier spline
+/// Third Order Bezier spline - if `closed` is set, the last two control points in `points` lead back round to the
+/// first point, closing up the shape
+#[derive(Debug, Clone, PartialEq)]
 pub struct BezierThirdSpline {
     pub points: Vec<Point>,
+    pub closed: bool,
 }
 
 impl BezierThirdSpline {
     pub fn new(points: Vec<Point>) -> Self {
-        Self { points }
+        Self {
+            points,
+            closed: false,
+        }
+    }
+
+    /// the spline closed back round to the first point
+    pub fn closed(points: Vec<Point>) -> Self {
+        Self {
+            points,
+            closed: true,
+        }
+    }
+
+    /// returns the number of pieces in the spline
+    fn count(&self) -> usize {
+        (self.points.len() + self.closed as usize).saturating_sub(1) / 3
+    }
+
+    /// returns piece `i` of the spline
+    fn segment(&self, i: usize) -> BezierThird {
+        let p = |k: usize| self.points[(i * 3 + k) % self.points.len()];
+        BezierThird::new(p(0), p(3), p(1), p(2))
+    }
+
+    /// returns the pieces of the spline in order
+    pub fn segments(&self) -> impl Iterator<Item = BezierThird> + '_ {
+        (0..self.count()).map(|i| self.segment(i))
+    }
+
+    /// returns the joins between the pieces that aren't smooth to within `tolerance`, see [`ContinuityBreak`]
+    pub fn analyze_continuity(&self, tolerance: f32) -> Vec<ContinuityBreak> {
+        let segments: Vec<_> = self.segments().collect();
+        continuity_breaks(
+            segments.iter().map(|s| s as &dyn ParametricFunction2D),
+            self.closed,
+            tolerance,
+        )
+    }
+
+    /// the C1 spline passing through every one of `points`, with the control points picked automatically as for a
+    /// cardinal spline - `tension` 0 gives the Catmull-Rom spline and 1 straight lines between the points. A single
+    /// point gives a spline which stays put.
+    pub fn smooth_through(points: &[Point], tension: f32) -> Self {
+        let n = points.len();
+        if n < 2 {
+            return Self::new(points.iter().flat_map(|&p| [p; 4]).collect());
+        }
+
+        let scale = (1.0 - tension) / 6.0;
+        let mut spline_points = vec![points[0]];
+        for i in 0..n - 1 {
+            let p0 = points[i.saturating_sub(1)];
+            let p1 = points[i];
+            let p2 = points[i + 1];
+            let p3 = points[(i + 2).min(n - 1)];
+
+            spline_points.push(p1 + (p2 - p0) * scale);
+            spline_points.push(p2 - (p3 - p1) * scale);
+            spline_points.push(p2);
+        }
+        Self::new(spline_points)
+    }
+
+    /// as [`BezierThirdSpline::smooth_through`], but closed up smoothly with a last piece leading from the last of
+    /// `points` back to the first
+    pub fn smooth_through_closed(points: &[Point], tension: f32) -> Self {
+        let n = points.len();
+        if n < 2 {
+            return Self::smooth_through(points, tension);
+        }
+
+        let scale = (1.0 - tension) / 6.0;
+        let mut spline_points = vec![];
+        for i in 0..n {
+            let p0 = points[(i + n - 1) % n];
+            let p1 = points[i];
+            let p2 = points[(i + 1) % n];
+            let p3 = points[(i + 2) % n];
+
+            spline_points.push(p1);
+            spline_points.push(p1 + (p2 - p0) * scale);
+            spline_points.push(p2 - (p3 - p1) * scale);
+        }
+        Self::closed(spline_points)
     }
 }
 
 impl ParametricFunction2D for BezierThirdSpline {
     fn evaluate(&self, t: T) -> Point {
-        let step = 3;
-        let bs: Vec<_> = self
-            .points
-            .windows(4)
-            .enumerate()
-            .filter(|&(i, _)| i % step == 0)
-            .map(|(_, t)| {
-                let t = t.to_vec();
-                Rc::new(Box::new(BezierThird {
-                    start: t[0],
-                    end: t[3],
-                    control1: t[1],
-                    control2: t[2],
-                }) as Box<dyn ParametricFunction2D>)
-            })
-            .collect();
-
-        let concat = Concat { functions: bs };
-        concat.evaluate(t)
+        match locate(self.count(), t) {
+            Some((i, t)) => self.segment(i).evaluate(t),
+            None => self.points.first().copied().unwrap_or_else(Point::origin),
+        }
     }
 }
 
-/// Fourth Order Bezier spline
+/// Fourth Order Bezier spline - if `closed` is set, the last three control points in `points` lead back round to
+/// the first point, closing up the shape
+#[derive(Debug, Clone, PartialEq)]
 pub struct BezierFourthSpline {
     pub points: Vec<Point>,
+    pub closed: bool,
 }
 
 impl BezierFourthSpline {
     pub fn new(points: Vec<Point>) -> Self {
-        Self { points }
+        Self {
+            points,
+            closed: false,
+        }
+    }
+
+    /// the spline closed back round to the first point
+    pub fn closed(points: Vec<Point>) -> Self {
+        Self {
+            points,
+            closed: true,
+        }
+    }
+
+    /// returns the number of pieces in the spline
+    fn count(&self) -> usize {
+        (self.points.len() + self.closed as usize).saturating_sub(1) / 4
+    }
+
+    /// returns piece `i` of the spline
+    fn segment(&self, i: usize) -> BezierFourth {
+        let p = |k: usize| self.points[(i * 4 + k) % self.points.len()];
+        BezierFourth::new(p(0), p(4), p(1), p(2), p(3))
+    }
+
+    /// returns the pieces of the spline in order
+    pub fn segments(&self) -> impl Iterator<Item = BezierFourth> + '_ {
+        (0..self.count()).map(|i| self.segment(i))
+    }
+
+    /// returns the joins between the pieces that aren't smooth to within `tolerance`, see [`ContinuityBreak`]
+    pub fn analyze_continuity(&self, tolerance: f32) -> Vec<ContinuityBreak> {
+        let segments: Vec<_> = self.segments().collect();
+        continuity_breaks(
+            segments.iter().map(|s| s as &dyn ParametricFunction2D),
+            self.closed,
+            tolerance,
+        )
     }
 }
 
 impl ParametricFunction2D for BezierFourthSpline {
     fn evaluate(&self, t: T) -> Point {
-        let step = 4;
-        let bs: Vec<_> = self
+        match locate(self.count(), t) {
+            Some((i, t)) => self.segment(i).evaluate(t),
+            None => self.points.first().copied().unwrap_or_else(Point::origin),
+        }
+    }
+}
+
+/// Bezier curve of any order, with its `N` control points in order from the start to the end (so `N - 1` is the
+/// order) - evaluated with de Casteljau's algorithm. No control points at all give the origin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BezierNth<const N: usize> {
+    pub points: [Point; N],
+}
+
+impl<const N: usize> BezierNth<N> {
+    pub fn new(points: [Point; N]) -> Self {
+        Self { points }
+    }
+
+    /// splits the curve at `t` into the two curves either side, each of the same order and each running over its
+    /// own full range of `t` - together they trace exactly the same shape
+    pub fn subdivide(&self, t: T) -> (Self, Self) {
+        let mut points = self.points;
+        let (mut left, mut right) = (self.points, self.points);
+        for k in (0..N).rev() {
+            left[N - 1 - k] = points[0];
+            right[k] = points[k];
+            for i in 0..k {
+                points[i] = points[i].lerp(points[i + 1], t.value());
+            }
+        }
+        (Self::new(left), Self::new(right))
+    }
+
+    /// returns the derivative with respect to `t` as a curve of its own (the hodograph), with the points on it
+    /// standing for vectors - it has one control point fewer, so `M` must be `N - 1`, which is checked at compile
+    /// time
+    pub fn derivative_curve<const M: usize>(&self) -> BezierNth<M> {
+        let () = DerivativeOrder::<N, M>::CHECK;
+        let order = M as f32;
+        BezierNth::new(std::array::from_fn(|i| {
+            ((self.points[i + 1] - self.points[i]) * order).to_point()
+        }))
+    }
+}
+
+impl<const N: usize> ParametricFunction2D for BezierNth<N> {
+    fn evaluate(&self, t: T) -> Point {
+        de_casteljau(&mut self.points.clone(), t)
+    }
+
+    fn derivative(&self, t: T) -> Vector {
+        let order = N.saturating_sub(1) as f32;
+        let mut hodograph: Vec<Point> = self
             .points
-            .windows(5)
-            .enumerate()
-            .filter(|&(i, _)| i % step == 0)
-            .map(|(_, t)| {
-                Rc::new(Box::new(BezierFourth {
-                    start: t[0],
-                    end: t[4],
-                    control1: t[1],
-                    control2: t[2],
-                    control3: t[3],
-                }) as Box<dyn ParametricFunction2D>)
-            })
+            .windows(2)
+            .map(|w| ((w[1] - w[0]) * order).to_point())
             .collect();
+        de_casteljau(&mut hodograph, t).to_vector()
+    }
+}
+
+/// evaluates the Bezier curve with control `points` at `t`, using them up as scratch space - no points at all give
+/// the origin
+/// fails to compile when a [`BezierNth::derivative_curve`] of a `BezierNth<N>` is asked for as anything but a
+/// `BezierNth<N - 1>`
+struct DerivativeOrder<const N: usize, const M: usize>;
+
+impl<const N: usize, const M: usize> DerivativeOrder<N, M> {
+    const CHECK: () = assert!(
+        M + 1 == N,
+        "the derivative of a BezierNth<N> is a BezierNth<N - 1>"
+    );
+}
+
+fn de_casteljau(points: &mut [Point], t: T) -> Point {
+    for k in (1..points.len()).rev() {
+        for i in 0..k {
+            points[i] = points[i].lerp(points[i + 1], t.value());
+        }
+    }
+    points.first().copied().unwrap_or_else(Point::origin)
+}
+
+impl From<BezierSecond> for BezierNth<3> {
+    fn from(b: BezierSecond) -> Self {
+        Self::new([b.start, b.control, b.end])
+    }
+}
+
+impl From<BezierNth<3>> for BezierSecond {
+    fn from(b: BezierNth<3>) -> Self {
+        let [start, control, end] = b.points;
+        Self::new(start, end, control)
+    }
+}
 
-        let concat = Concat { functions: bs };
-        concat.evaluate(t)
+impl From<BezierThird> for BezierNth<4> {
+    fn from(b: BezierThird) -> Self {
+        Self::new([b.start, b.control1, b.control2, b.end])
     }
 }
 
-// THIS IS PROBABLY POSSIBLE!! Lets Stop at 4th order for now!
+impl From<BezierNth<4>> for BezierThird {
+    fn from(b: BezierNth<4>) -> Self {
+        let [start, control1, control2, end] = b.points;
+        Self::new(start, end, control1, control2)
+    }
+}
+
+impl From<BezierFourth> for BezierNth<5> {
+    fn from(b: BezierFourth) -> Self {
+        Self::new([b.start, b.control1, b.control2, b.control3, b.end])
+    }
+}
+
+impl From<BezierNth<5>> for BezierFourth {
+    fn from(b: BezierNth<5>) -> Self {
+        let [start, control1, control2, control3, end] = b.points;
+        Self::new(start, end, control1, control2, control3)
+    }
+}
 
-// struct BezierNth<const N: usize> {
-//     points: [(f32, f32); N],
-// }
+/// The segment as a degenerate curve, with the control point halfway along it so it's traversed at the same speed
+impl From<Segment> for BezierSecond {
+    fn from(s: Segment) -> Self {
+        Self::new(s.start, s.end, s.start.lerp(s.end, 0.5))
+    }
+}
 
-// impl<const N: usize> ParametricFunction2D for BezierNth<N> {
-//     fn calculate(&self, t: T) -> (f32, f32) {
-//         let segments: Vec<Segment> = self
-//             .points
-//             .windows(2)
-//             .map(|[x, y]| Segment { start: *x, end: *y })
-//             .collect();
+/// The segment as a degenerate curve, with the control points a third of the way from each end so it's traversed
+/// at the same speed
+impl From<Segment> for BezierThird {
+    fn from(s: Segment) -> Self {
+        Self::new(
+            s.start,
+            s.end,
+            s.start.lerp(s.end, 1.0 / 3.0),
+            s.start.lerp(s.end, 2.0 / 3.0),
+        )
+    }
+}
 
-//         let points: Vec<(f32, f32)> = segments.iter().map(|s| s.calculate(t)).collect();
-//     }
-// }
+/// Degree elevation - the cubic traces exactly the same curve, with the same parameterisation
+impl From<BezierSecond> for BezierThird {
+    fn from(b: BezierSecond) -> Self {
+        Self::new(
+            b.start,
+            b.end,
+            b.start.lerp(b.control, 2.0 / 3.0),
+            b.end.lerp(b.control, 2.0 / 3.0),
+        )
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use approx::assert_relative_eq;
 
+    #[test]
+    fn test_from_lower_order() {
+        let s = Segment::new((0.0, 0.0).into(), (3.0, 6.0).into());
+        let q = BezierSecond::from(s.clone());
+        let c = BezierThird::from(s.clone());
+        for t in T::range(5) {
+            let expected = s.evaluate(t);
+            assert_relative_eq!(q.evaluate(t).x, expected.x, epsilon = 1e-5);
+            assert_relative_eq!(q.evaluate(t).y, expected.y, epsilon = 1e-5);
+            assert_relative_eq!(c.evaluate(t).x, expected.x, epsilon = 1e-5);
+            assert_relative_eq!(c.evaluate(t).y, expected.y, epsilon = 1e-5);
+        }
+
+        let q = BezierSecond::new((0.0, 0.0).into(), (2.0, 0.0).into(), (1.0, 2.0).into());
+        let c = BezierThird::from(q.clone());
+        for t in T::range(7) {
+            assert_relative_eq!(c.evaluate(t).x, q.evaluate(t).x, epsilon = 1e-5);
+            assert_relative_eq!(c.evaluate(t).y, q.evaluate(t).y, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_clone_eq() {
+        let b = BezierThird::new(
+            (0.0, 0.0).into(),
+            (3.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (2.0, 1.0).into(),
+        );
+        let mut c = b.clone();
+        assert_eq!(b, c);
+        c.control1 = (1.0, 2.0).into();
+        assert_ne!(b, c);
+
+        let splines = vec![BezierThirdSpline::new(vec![b.start, b.control1, b.control2, b.end]); 2];
+        assert_eq!(splines[0], splines[1]);
+    }
+
     #[test]
     fn test_bezier_second() {
         let b = BezierSecond::new((0.0, 0.0).into(), (2.0, 0.0).into(), (1.0, 1.0).into());
@@ -491,4 +889,275 @@ mod tests {
         assert_relative_eq!(res.x, 3.0);
         assert_relative_eq!(res.y, 0.6875);
     }
+
+    #[test]
+    fn test_bezier_nth() {
+        // the same as the fixed order curves
+        let b: BezierNth<5> = BezierFourth::new(
+            (0.0, 0.0).into(),
+            (2.0, 0.0).into(),
+            (0.5, 1.0).into(),
+            (1.0, 0.5).into(),
+            (1.5, 1.0).into(),
+        )
+        .into();
+
+        let res = b.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.6875);
+
+        let c: BezierThird = BezierNth::new([
+            (0.0, 0.0).into(),
+            (0.0, 1.0).into(),
+            (1.0, 1.0).into(),
+            (1.0, 0.0).into(),
+        ])
+        .into();
+        assert_relative_eq!(c.control2.x, 1.0);
+        assert_relative_eq!(c.end.y, 0.0);
+
+        // and orders beyond them
+        let b = BezierNth::new([
+            (0.0, 0.0).into(),
+            (1.0, 2.0).into(),
+            (2.0, -2.0).into(),
+            (3.0, 2.0).into(),
+            (4.0, -2.0).into(),
+            (5.0, 0.0).into(),
+        ]);
+
+        let res = b.start();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = b.end();
+        assert_relative_eq!(res.x, 5.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        // evenly spaced x control points give a linear x
+        let res = b.evaluate(T::new(0.3));
+        assert_relative_eq!(res.x, 1.5, epsilon = 1e-6);
+
+        let res = BezierNth::new([Point::new(1.0, 2.0)]).evaluate(T::new(0.3));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 2.0);
+        let res = BezierNth::<0>::new([]).evaluate(T::new(0.3));
+        assert_relative_eq!(res.x, 0.0);
+    }
+
+    #[test]
+    fn test_subdivide() {
+        let b = BezierThird::new(
+            (0.0, 0.0).into(),
+            (1.0, 0.0).into(),
+            (0.0, 1.0).into(),
+            (1.0, 1.0).into(),
+        );
+        let (left, right) = b.subdivide(T::new(0.5));
+
+        assert_relative_eq!(left.end.x, 0.5);
+        assert_relative_eq!(left.end.y, 0.75);
+        assert_relative_eq!(right.start.x, 0.5);
+        assert_relative_eq!(right.start.y, 0.75);
+
+        // the halves trace the same curve
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            let expected = b.evaluate(T::new(t * 0.5));
+            let res = left.evaluate(T::new(t));
+            assert_relative_eq!(res.x, expected.x, epsilon = 1e-6);
+            assert_relative_eq!(res.y, expected.y, epsilon = 1e-6);
+
+            let expected = b.evaluate(T::new(0.5 + t * 0.5));
+            let res = right.evaluate(T::new(t));
+            assert_relative_eq!(res.x, expected.x, epsilon = 1e-6);
+            assert_relative_eq!(res.y, expected.y, epsilon = 1e-6);
+        }
+
+        let b = BezierSecond::new((0.0, 0.0).into(), (2.0, 0.0).into(), (1.0, 1.0).into());
+        let (left, right) = b.subdivide(T::new(0.25));
+        assert_relative_eq!(left.end.x, 0.5);
+        assert_relative_eq!(left.end.y, 0.375);
+        assert_relative_eq!(left.control.x, 0.25);
+        assert_relative_eq!(left.control.y, 0.25);
+        assert_relative_eq!(right.end.x, 2.0);
+
+        let b = BezierFourth::new(
+            (0.0, 0.0).into(),
+            (2.0, 0.0).into(),
+            (0.5, 1.0).into(),
+            (1.0, 0.5).into(),
+            (1.5, 1.0).into(),
+        );
+        let (left, right) = b.subdivide(T::new(0.3));
+        let expected = b.evaluate(T::new(0.3));
+        assert_relative_eq!(left.end.x, expected.x, epsilon = 1e-6);
+        assert_relative_eq!(right.start.y, expected.y, epsilon = 1e-6);
+        let res = right.evaluate(T::new(0.5));
+        let expected = b.evaluate(T::new(0.65));
+        assert_relative_eq!(res.x, expected.x, epsilon = 1e-6);
+        assert_relative_eq!(res.y, expected.y, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_derivative_curve() {
+        let b = BezierThird::new(
+            (0.0, 0.0).into(),
+            (1.0, 0.0).into(),
+            (0.0, 1.0).into(),
+            (1.0, 1.0).into(),
+        );
+        let d = b.derivative_curve();
+
+        let res = d.start;
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 3.0);
+
+        // at the top of the curve it's moving straight across
+        let res = b.derivative(T::new(0.5));
+        assert_relative_eq!(res.x, 1.5);
+        assert_relative_eq!(res.y, 0.0);
+
+        // and agrees with the general order curve
+        let res = b.derivative(T::new(0.3));
+        let expected = BezierNth::from(b)
+            .derivative_curve::<3>()
+            .evaluate(T::new(0.3));
+        assert_relative_eq!(res.x, expected.x, epsilon = 1e-6);
+        assert_relative_eq!(res.y, expected.y, epsilon = 1e-6);
+
+        let b = BezierSecond::new((0.0, 0.0).into(), (2.0, 0.0).into(), (1.0, 1.0).into());
+        let res = b.derivative(T::end());
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, -2.0);
+
+        let b = BezierFourth::new(
+            (0.0, 0.0).into(),
+            (2.0, 0.0).into(),
+            (0.5, 1.0).into(),
+            (1.0, 0.5).into(),
+            (1.5, 1.0).into(),
+        );
+        let res = b.derivative(T::start());
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 4.0);
+        let res = BezierNth::from(b).derivative(T::start());
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 4.0);
+    }
+
+    #[test]
+    fn test_smooth_through() {
+        let points: Vec<Point> = vec![
+            (0.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (2.0, 0.0).into(),
+            (3.0, 1.0).into(),
+        ];
+        let b = BezierThirdSpline::smooth_through(&points, 0.0);
+        assert_eq!(b.points.len(), 10);
+
+        for (i, p) in points.iter().enumerate() {
+            let res = b.evaluate(T::new(i as f32 / 3.0));
+            assert_relative_eq!(res.x, p.x, epsilon = 1e-5);
+            assert_relative_eq!(res.y, p.y, epsilon = 1e-5);
+        }
+
+        // the tangents match where the pieces meet
+        let before = b.points[5] - b.points[6];
+        let after = b.points[7] - b.points[6];
+        assert_relative_eq!(before.cross(after), 0.0, epsilon = 1e-6);
+        assert!(before.dot(after) < 0.0);
+
+        // full tension pulls the control points onto the lines between
+        let b = BezierThirdSpline::smooth_through(&points, 1.0);
+        let res = b.evaluate(T::new(1.0 / 6.0));
+        assert_relative_eq!(res.x, 0.5, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.5, epsilon = 1e-5);
+
+        let b = BezierThirdSpline::smooth_through(&points[..1], 0.5);
+        let res = b.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+    }
+
+    #[test]
+    fn test_closed_splines() {
+        let b = BezierSecondSpline::closed(
+            vec![(0.0, 0.0), (1.0, 1.0), (2.0, 0.0), (1.0, -1.0)]
+                .into_iter()
+                .map(|p| p.into())
+                .collect(),
+        );
+
+        let res = b.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = b.end();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = b.evaluate(T::new(0.75));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, -0.5);
+
+        // a smooth blob through the corners of a square
+        let square: Vec<Point> = vec![
+            (0.0, 0.0).into(),
+            (1.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (0.0, 1.0).into(),
+        ];
+        let b = BezierThirdSpline::smooth_through_closed(&square, 0.0);
+        assert_eq!(b.points.len(), 12);
+
+        let res = b.end();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        // with matching tangents at the start and end
+        let before = b.points[11] - b.points[0];
+        let after = b.points[1] - b.points[0];
+        assert_relative_eq!(before.cross(after), 0.0, epsilon = 1e-6);
+        assert!(before.dot(after) < 0.0);
+    }
+
+    #[test]
+    fn test_segments() {
+        let b = BezierThirdSpline::new(
+            vec![
+                (0.0, 0.0),
+                (0.0, 1.0),
+                (1.0, 1.0),
+                (1.0, 0.0),
+                (1.0, 1.0),
+                (2.0, 1.0),
+                (2.0, 0.0),
+                (5.0, 5.0),
+            ]
+            .into_iter()
+            .map(|p| p.into())
+            .collect(),
+        );
+
+        // leftover points that don't make a whole piece are ignored
+        let segments: Vec<BezierThird> = b.segments().collect();
+        assert_eq!(segments.len(), 2);
+        assert_relative_eq!(segments[1].start.x, 1.0);
+        assert_relative_eq!(segments[1].control1.y, 1.0);
+        assert_relative_eq!(segments[1].end.x, 2.0);
+
+        let b = BezierSecondSpline::closed(
+            vec![(0.0, 0.0), (1.0, 1.0), (2.0, 0.0), (1.0, -1.0)]
+                .into_iter()
+                .map(|p| p.into())
+                .collect(),
+        );
+        let last = b.segments().last().unwrap();
+        assert_relative_eq!(last.end.x, 0.0);
+        assert_relative_eq!(last.control.y, -1.0);
+
+        assert_eq!(BezierFourthSpline::new(vec![]).segments().count(), 0);
+    }
 }
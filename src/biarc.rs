@@ -0,0 +1,222 @@
+//! Approximation by biarcs - pairs of circular arcs meeting with a shared tangent
+
+use crate::{
+    core::{ParametricFunction2D, Point, Vector, MAX_DEPTH, T},
+    ellipse::EllipseArc,
+    segment::Segment,
+};
+
+/// A piece of a biarc approximation, see [`ParametricFunction2D::to_biarcs`] - arcs are [`EllipseArc`]s with equal
+/// radii, and a straight line is used where an arc would be too flat to tell apart from one
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArcOrLine {
+    Line(Segment),
+    Arc(EllipseArc),
+}
+
+impl ParametricFunction2D for ArcOrLine {
+    fn evaluate(&self, t: T) -> Point {
+        match self {
+            ArcOrLine::Line(line) => line.evaluate(t),
+            ArcOrLine::Arc(arc) => arc.evaluate(t),
+        }
+    }
+}
+
+/// approximates `function` with biarcs within `tolerance`, see [`ParametricFunction2D::to_biarcs`]
+pub(crate) fn biarcs<F: ParametricFunction2D + ?Sized>(
+    function: &F,
+    tolerance: f32,
+) -> Vec<ArcOrLine> {
+    let mut pieces = vec![];
+    push_biarcs(function, (0.0, 1.0), tolerance, MAX_DEPTH, &mut pieces);
+    pieces
+}
+
+/// pushes the pieces of the biarc(s) approximating the range `a..b`
+fn push_biarcs<F: ParametricFunction2D + ?Sized>(
+    function: &F,
+    (a, b): (f32, f32),
+    tolerance: f32,
+    depth: usize,
+    pieces: &mut Vec<ArcOrLine>,
+) {
+    let (p0, p1) = (function.evaluate(T::new(a)), function.evaluate(T::new(b)));
+    let chord = (p1 - p0).try_normalize().unwrap_or_else(Vector::zero);
+    let tangent = |t: f32| {
+        let tangent = function.tangent(T::new(t));
+        if tangent == Vector::zero() {
+            chord
+        } else {
+            tangent
+        }
+    };
+    let biarc = biarc(p0, tangent(a), p1, tangent(b));
+
+    let close_enough = (1..8).all(|i| {
+        let expected = function.evaluate(T::new(a + (b - a) * i as f32 / 8.0));
+        biarc
+            .iter()
+            .map(|piece| piece.distance_to(expected, 16))
+            .fold(f32::INFINITY, f32::min)
+            <= tolerance
+    });
+
+    if close_enough || depth == 0 {
+        pieces.extend(biarc);
+    } else {
+        let mid = (a + b) / 2.0;
+        push_biarcs(function, (a, mid), tolerance, depth - 1, pieces);
+        push_biarcs(function, (mid, b), tolerance, depth - 1, pieces);
+    }
+}
+
+/// the two arcs from `p0` heading along `t0` to `p1` heading along `t1`, meeting where the tangent lines through
+/// each end are the same distance from it
+fn biarc(p0: Point, t0: Vector, p1: Point, t1: Vector) -> Vec<ArcOrLine> {
+    let v = p1 - p0;
+    if v.length() <= f32::EPSILON {
+        return vec![];
+    }
+
+    let t = t0 + t1;
+    let (vt, tt) = (v.dot(t), t.dot(t));
+    let a = tt - 4.0;
+    let d = if a.abs() <= 1e-6 {
+        if vt.abs() <= 1e-6 {
+            // the tangents are equal and at right angles to the chord
+            v.length() / 2.0
+        } else {
+            v.square_length() / (2.0 * vt)
+        }
+    } else {
+        (vt - (vt * vt - a * v.square_length()).max(0.0).sqrt()) / a
+    };
+
+    let joint = ((p0 + t0 * d).to_vector() + (p1 - t1 * d).to_vector()) / 2.0;
+    let joint = joint.to_point();
+    vec![arc(p0, t0, joint, false), arc(p1, t1, joint, true)]
+}
+
+/// the arc (or line) leaving `p` along `tangent` to `q` - or if `reversed`, arriving at `p` along `tangent` from `q`
+fn arc(p: Point, tangent: Vector, q: Point, reversed: bool) -> ArcOrLine {
+    let line = || {
+        if reversed {
+            ArcOrLine::Line(Segment::new(q, p))
+        } else {
+            ArcOrLine::Line(Segment::new(p, q))
+        }
+    };
+
+    let w = q - p;
+    let normal = Vector::new(-tangent.y, tangent.x);
+    let along = normal.dot(w);
+    if along.abs() <= 1e-6 * w.square_length().max(f32::EPSILON) {
+        return line();
+    }
+
+    // signed so that positive radii turn left
+    let radius = w.square_length() / (2.0 * along);
+    let centre = p + normal * radius;
+
+    let (from, to) = if reversed { (q, p) } else { (p, q) };
+    let (u, v) = (from - centre, to - centre);
+    let mut sweep = u.cross(v).atan2(u.dot(v));
+    if radius > 0.0 && sweep < 0.0 {
+        sweep += std::f32::consts::TAU;
+    } else if radius < 0.0 && sweep > 0.0 {
+        sweep -= std::f32::consts::TAU;
+    }
+
+    let start = u.y.atan2(u.x);
+    let turns = |radians: f32| radians / std::f32::consts::TAU;
+    ArcOrLine::Arc(EllipseArc::new(
+        centre,
+        radius.abs(),
+        radius.abs(),
+        0.0,
+        turns(start),
+        turns(start + sweep),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bezier::{BezierThird, BezierThirdSpline};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_biarc() {
+        // a semicircle needs just the one biarc, made of two quarter circles
+        let pieces = biarc(
+            (1.0, 0.0).into(),
+            (0.0, 1.0).into(),
+            (-1.0, 0.0).into(),
+            (0.0, -1.0).into(),
+        );
+        assert_eq!(pieces.len(), 2);
+        for piece in &pieces {
+            match piece {
+                ArcOrLine::Arc(arc) => {
+                    assert_relative_eq!(arc.radius_x, 1.0, epsilon = 1e-5);
+                    assert_relative_eq!(arc.centre.x, 0.0, epsilon = 1e-5);
+                    assert_relative_eq!(arc.end_angle - arc.start_angle, 0.25, epsilon = 1e-5);
+                }
+                ArcOrLine::Line(_) => panic!("expected an arc"),
+            }
+        }
+        let res = pieces[0].end();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+
+        // and straight lines stay straight
+        let pieces = biarc(
+            (0.0, 0.0).into(),
+            (1.0, 0.0).into(),
+            (2.0, 0.0).into(),
+            (1.0, 0.0).into(),
+        );
+        assert!(matches!(pieces[0], ArcOrLine::Line(_)));
+        assert!(matches!(pieces[1], ArcOrLine::Line(_)));
+        let res = pieces[1].end();
+        assert_relative_eq!(res.x, 2.0);
+    }
+
+    #[test]
+    fn test_to_biarcs() {
+        let b = BezierThird::new(
+            (0.0, 0.0).into(),
+            (3.0, 0.0).into(),
+            (1.0, 2.0).into(),
+            (2.0, -2.0).into(),
+        );
+        let pieces = b.to_biarcs(1e-3);
+        assert!(pieces.len() >= 4);
+        assert_eq!(pieces.len() % 2, 0);
+
+        // joined up end to end, with matching tangents
+        for pair in pieces.windows(2) {
+            let (a, b) = (pair[0].end(), pair[1].start());
+            assert_relative_eq!(a.x, b.x, epsilon = 1e-4);
+            assert_relative_eq!(a.y, b.y, epsilon = 1e-4);
+            let (a, b) = (pair[0].tangent(T::end()), pair[1].tangent(T::start()));
+            assert_relative_eq!(a.dot(b), 1.0, epsilon = 1e-2);
+        }
+        for p in b.linspace(50) {
+            let distance = pieces
+                .iter()
+                .map(|piece| piece.distance_to(p, 16))
+                .fold(f32::INFINITY, f32::min);
+            assert!(distance <= 2e-3);
+        }
+
+        let spline = BezierThirdSpline::smooth_through(
+            &[(0.0, 0.0).into(), (1.0, 1.0).into(), (2.0, 0.0).into()],
+            0.0,
+        );
+        let res = spline.to_biarcs(1e-2).last().unwrap().end();
+        assert_relative_eq!(res.x, 2.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-5);
+    }
+}
@@ -0,0 +1,192 @@
+//! B-splines
+
+use crate::{
+    core::{ParametricFunction2D, Point, T},
+    error::ParametricsError,
+};
+
+/// A B-spline of `degree` with its `control_points` and non-decreasing `knots` - there must be exactly
+/// `control_points.len() + degree + 1` knots. Moving a control point only changes the curve over the `degree + 1`
+/// spans around it. `t` runs over the valid part of the knot vector, from knot `degree` to knot
+/// `control_points.len()`, evaluated with the Cox-de Boor recursion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BSpline {
+    pub control_points: Vec<Point>,
+    pub degree: usize,
+    pub knots: Vec<f32>,
+}
+
+impl BSpline {
+    /// fails if there are fewer than `degree + 1` control points or the knots don't fit them, see [`BSpline`]
+    pub fn new(
+        control_points: Vec<Point>,
+        degree: usize,
+        knots: Vec<f32>,
+    ) -> Result<Self, ParametricsError> {
+        if control_points.len() <= degree {
+            return Err(ParametricsError::TooFewPoints {
+                needed: degree + 1,
+                found: control_points.len(),
+            });
+        }
+        if knots.len() != control_points.len() + degree + 1
+            || knots.windows(2).any(|w| w[1] < w[0])
+            || knots[degree] >= knots[control_points.len()]
+        {
+            return Err(ParametricsError::InvalidKnots);
+        }
+        Ok(Self {
+            control_points,
+            degree,
+            knots,
+        })
+    }
+
+    /// the B-spline with evenly spaced knots - it starts and ends away from the first and last control points
+    pub fn uniform(control_points: Vec<Point>, degree: usize) -> Result<Self, ParametricsError> {
+        let count = control_points.len() + degree + 1;
+        let knots = (0..count).map(|i| i as f32 / (count - 1) as f32).collect();
+        Self::new(control_points, degree, knots)
+    }
+
+    /// the B-spline with evenly spaced knots apart from `degree + 1` repeated at each end, so it starts at the first
+    /// control point and ends at the last - with `degree + 1` control points this is a Bezier curve
+    pub fn clamped(control_points: Vec<Point>, degree: usize) -> Result<Self, ParametricsError> {
+        let spans = control_points.len().saturating_sub(degree).max(1);
+        let mut knots = vec![0.0; degree];
+        knots.extend((0..=spans).map(|i| i as f32 / spans as f32));
+        knots.resize(knots.len() + degree, 1.0);
+        Self::new(control_points, degree, knots)
+    }
+
+    /// returns the values of the `degree + 1` basis functions that are non-zero at `u` in the knot span `span`
+    fn basis(&self, span: usize, u: f32) -> Vec<f32> {
+        let p = self.degree;
+        let mut values = vec![0.0; p + 1];
+        let mut left = vec![0.0; p + 1];
+        let mut right = vec![0.0; p + 1];
+        values[0] = 1.0;
+
+        for j in 1..=p {
+            left[j] = u - self.knots[span + 1 - j];
+            right[j] = self.knots[span + j] - u;
+            let mut saved = 0.0;
+            for r in 0..j {
+                let temp = values[r] / (right[r + 1] + left[j - r]);
+                values[r] = saved + right[r + 1] * temp;
+                saved = left[j - r] * temp;
+            }
+            values[j] = saved;
+        }
+        values
+    }
+}
+
+impl ParametricFunction2D for BSpline {
+    fn evaluate(&self, t: T) -> Point {
+        let (p, n) = (self.degree, self.control_points.len());
+        if n <= p || self.knots.len() != n + p + 1 {
+            return self
+                .control_points
+                .first()
+                .copied()
+                .unwrap_or_else(Point::origin);
+        }
+
+        let (a, b) = (self.knots[p], self.knots[n]);
+        let u = a + (b - a) * t.value();
+        let span = (p..n)
+            .rev()
+            .find(|&k| self.knots[k] <= u && self.knots[k] < self.knots[k + 1])
+            .unwrap_or(p);
+
+        self.basis(span, u)
+            .into_iter()
+            .enumerate()
+            .fold(Point::origin(), |sum, (r, value)| {
+                sum + self.control_points[span - p + r].to_vector() * value
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bezier::BezierThird;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_uniform_bspline() {
+        let points: Vec<Point> = vec![(0.0, 0.0).into(), (1.0, 2.0).into(), (2.0, 0.0).into()];
+        let b = BSpline::uniform(points, 2).unwrap();
+
+        // a single span, from midpoint to midpoint
+        let res = b.start();
+        assert_relative_eq!(res.x, 0.5);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = b.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.5);
+
+        let res = b.end();
+        assert_relative_eq!(res.x, 1.5);
+        assert_relative_eq!(res.y, 1.0);
+
+        // degree 1 is the polyline through the points
+        let points: Vec<Point> = vec![(0.0, 0.0).into(), (1.0, 1.0).into(), (3.0, 1.0).into()];
+        let b = BSpline::uniform(points, 1).unwrap();
+        let res = b.evaluate(T::new(0.75));
+        assert_relative_eq!(res.x, 2.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_clamped_bspline() {
+        let points: Vec<Point> = vec![
+            (0.0, 0.0).into(),
+            (0.0, 1.0).into(),
+            (1.0, 1.0).into(),
+            (1.0, 0.0).into(),
+        ];
+
+        // matches the Bezier with the same control points
+        let b = BSpline::clamped(points.clone(), 3).unwrap();
+        let bezier = BezierThird::new(points[0], points[3], points[1], points[2]);
+        for (a, b) in b.linspace(10).into_iter().zip(bezier.linspace(10)) {
+            assert_relative_eq!(a.x, b.x, epsilon = 1e-6);
+            assert_relative_eq!(a.y, b.y, epsilon = 1e-6);
+        }
+
+        // moving a control point only changes the nearby spans
+        let mut points: Vec<Point> = (0..8).map(|i| Point::new(i as f32, 0.0)).collect();
+        let b = BSpline::clamped(points.clone(), 2).unwrap();
+        points[7].y = 5.0;
+        let moved = BSpline::clamped(points, 2).unwrap();
+        let res = moved.evaluate(T::new(0.3));
+        assert_relative_eq!(res.y, b.evaluate(T::new(0.3)).y);
+        let res = moved.end();
+        assert_relative_eq!(res.x, 7.0);
+        assert_relative_eq!(res.y, 5.0);
+    }
+
+    #[test]
+    fn test_bspline_errors() {
+        let points: Vec<Point> = vec![(0.0, 0.0).into(), (1.0, 1.0).into()];
+        assert_eq!(
+            BSpline::uniform(points.clone(), 2).unwrap_err(),
+            ParametricsError::TooFewPoints {
+                needed: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            BSpline::new(points.clone(), 1, vec![0.0, 1.0]).unwrap_err(),
+            ParametricsError::InvalidKnots
+        );
+        assert_eq!(
+            BSpline::new(points, 1, vec![0.0, 1.0, 0.5, 1.0]).unwrap_err(),
+            ParametricsError::InvalidKnots
+        );
+    }
+}
@@ -1,53 +1,232 @@
 //! Circles and Rcs
 
-use crate::core::{ParametricFunction2D, Point, T};
+use crate::{
+    angle::Angle,
+    bezier::BezierThirdSpline,
+    core::{ParametricFunction2D, Point, Vector, T},
+    error::{check_finite, check_points, check_radius, ParametricsError},
+};
 
-/// A circle of radius `r`, centred at a point - parameterisation starting at a given "angle"
-/// measured in "turns" (so `[0,1]`) - where `0` is on the positive x-axis for the unit circle.
+/// A circle of radius `r`, centred at a point - parameterisation starting at a given [`Angle`], where `0` is on the
+/// positive x-axis for the unit circle.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Circle {
     pub centre: Point,
     pub radius: f32,
-    pub start_angle: T,
+    pub start_angle: Angle,
 }
 
 impl Circle {
+    /// `start_angle` is in "turns", see [`Circle::with_angle`] for an [`Angle`] in other units
     pub fn new(centre: Point, radius: f32, start_angle: Option<T>) -> Self {
+        Self::with_angle(centre, radius, start_angle.map(Angle::from))
+    }
+
+    /// as [`Circle::new`] but with the start given as an [`Angle`]
+    pub fn with_angle(centre: Point, radius: f32, start_angle: Option<Angle>) -> Self {
         Self {
             centre,
             radius,
-            start_angle: start_angle.unwrap_or(T::start()),
+            start_angle: start_angle.unwrap_or_default(),
         }
     }
+
+    /// as [`Circle::with_angle`] but fails if anything isn't finite or the radius isn't above zero
+    pub fn try_new(
+        centre: Point,
+        radius: f32,
+        start_angle: Option<Angle>,
+    ) -> Result<Self, ParametricsError> {
+        check_points(&[centre])?;
+        check_radius(radius)?;
+        check_finite(start_angle.map(|a| a.to_turns()))?;
+        Ok(Self::with_angle(centre, radius, start_angle))
+    }
+
+    /// approximates the circle with cubic Beziers, at least four and as many as it takes to stay within `max_error`
+    /// of it - see [`CircleArc::to_cubics`]
+    pub fn to_cubics(&self, max_error: f32) -> BezierThirdSpline {
+        arc_cubics(
+            self.centre,
+            self.radius,
+            self.start_angle.to_turns(),
+            1.0,
+            max_error,
+        )
+    }
 }
 
-/// A circle Rc of radius `r`, centred at a point - parameterisation starting at a given "angle" `start_angle`
-/// and ending at `end_angle` - "angles" are "turns" as described in [`Circle`]
+/// A circle Rc of radius `r`, centred at a point - parameterisation starting at a given [`Angle`] `start_angle`
+/// and ending at `end_angle`, going clockwise if that is the smaller of the two
+#[derive(Debug, Clone, PartialEq)]
 pub struct CircleArc {
     pub centre: Point,
     pub radius: f32,
-    pub start_angle: T,
-    pub end_angle: T,
+    pub start_angle: Angle,
+    pub end_angle: Angle,
 }
 
 impl CircleArc {
+    /// the angles are in "turns", see [`CircleArc::with_angles`] for [`Angle`]s in other units
     pub fn new(centre: Point, radius: f32, start_angle: Option<T>, end_angle: Option<T>) -> Self {
+        Self::with_angles(
+            centre,
+            radius,
+            start_angle.map(Angle::from),
+            end_angle.map(Angle::from),
+        )
+    }
+
+    /// as [`CircleArc::new`] but with the angles given as [`Angle`]s
+    pub fn with_angles(
+        centre: Point,
+        radius: f32,
+        start_angle: Option<Angle>,
+        end_angle: Option<Angle>,
+    ) -> Self {
         Self {
-            centre: centre,
-            radius: radius,
-            start_angle: start_angle.unwrap_or(T::start()),
-            end_angle: end_angle.unwrap_or(T::end()),
+            centre,
+            radius,
+            start_angle: start_angle.unwrap_or_default(),
+            end_angle: end_angle.unwrap_or(Angle::turns(1.0)),
         }
     }
+
+    /// as [`CircleArc::with_angles`] but fails if anything isn't finite or the radius isn't above zero
+    pub fn try_new(
+        centre: Point,
+        radius: f32,
+        start_angle: Option<Angle>,
+        end_angle: Option<Angle>,
+    ) -> Result<Self, ParametricsError> {
+        check_points(&[centre])?;
+        check_radius(radius)?;
+        check_finite(start_angle.iter().chain(&end_angle).map(|a| a.to_turns()))?;
+        Ok(Self::with_angles(centre, radius, start_angle, end_angle))
+    }
+
+    /// approximates the arc with cubic Beziers, splitting it into equal pieces each with its control points
+    /// `4/3 tan(angle / 4)` radii along the tangents at its ends - as many pieces as it takes to stay within
+    /// `max_error` of the arc
+    pub fn to_cubics(&self, max_error: f32) -> BezierThirdSpline {
+        arc_cubics(
+            self.centre,
+            self.radius,
+            self.start_angle.to_turns(),
+            self.end_angle.to_turns() - self.start_angle.to_turns(),
+            max_error,
+        )
+    }
+
+    /// the arc of the circle through `a`, `b` and `c` which starts at `a`, passes through `b` and ends at `c`. This
+    /// may cross the positive x-axis, which is fine as the angles of a [`CircleArc`] aren't clamped. Fails with
+    /// [`ParametricsError::Collinear`] if there's no such circle.
+    pub fn through_points(a: Point, b: Point, c: Point) -> Result<CircleArc, ParametricsError> {
+        // work relative to `a` to keep the arithmetic well conditioned
+        let (ab, ac) = (b - a, c - a);
+        let cross = ab.cross(ac);
+        if cross.abs() <= 1e-6 * ab.length() * ac.length() {
+            return Err(ParametricsError::Collinear);
+        }
+
+        let d = 2.0 * cross;
+        let (ab_sq, ac_sq) = (ab.square_length(), ac.square_length());
+        let centre = a + Vector::new(
+            (ac.y * ab_sq - ab.y * ac_sq) / d,
+            (ab.x * ac_sq - ac.x * ab_sq) / d,
+        );
+        let radius = (a - centre).length();
+
+        let angle = |p: Point| {
+            let offset = p - centre;
+            offset.y.atan2(offset.x) / std::f32::consts::TAU
+        };
+        let (start, end) = (angle(a), angle(c));
+
+        // turning left from a to b to c means going round anticlockwise
+        let sweep = if cross > 0.0 {
+            (end - start).rem_euclid(1.0)
+        } else {
+            -(start - end).rem_euclid(1.0)
+        };
+
+        Ok(CircleArc::with_angles(
+            centre,
+            radius,
+            Some(Angle::turns(start)),
+            Some(Angle::turns(start + sweep)),
+        ))
+    }
+}
+
+/// The arc as cubic Beziers of at most a quarter turn each, which stay within 0.06% of the radius of it - see
+/// [`CircleArc::to_cubics`] for a tolerance of your own. Fails if anything isn't finite or the radius isn't above
+/// zero.
+impl TryFrom<CircleArc> for BezierThirdSpline {
+    type Error = ParametricsError;
+
+    fn try_from(arc: CircleArc) -> Result<Self, Self::Error> {
+        check_points(&[arc.centre])?;
+        check_radius(arc.radius)?;
+        check_finite([arc.start_angle.to_turns(), arc.end_angle.to_turns()])?;
+        Ok(arc.to_cubics(arc.radius * 6e-4))
+    }
+}
+
+/// the cubic Bezier approximation to the arc around `centre` from `start` sweeping through `sweep`, both in "turns",
+/// see [`CircleArc::to_cubics`]
+fn arc_cubics(
+    centre: Point,
+    radius: f32,
+    start: f32,
+    sweep: f32,
+    max_error: f32,
+) -> BezierThirdSpline {
+    // the largest distance from the circle of a piece sweeping through `angle` radians
+    let error = |angle: f32| {
+        let quarter = angle.abs() / 4.0;
+        radius.abs() * 4.0 / 27.0 * quarter.sin().powi(6) / quarter.cos().powi(2)
+    };
+
+    let sweep = sweep * std::f32::consts::TAU;
+    let mut pieces = (sweep.abs() / std::f32::consts::FRAC_PI_2).ceil().max(1.0) as usize;
+    while error(sweep / pieces as f32) > max_error && pieces < 1024 {
+        pieces *= 2;
+    }
+
+    let angle = sweep / pieces as f32;
+    let reach = radius * 4.0 / 3.0 * (angle / 4.0).tan();
+    let at = |theta: f32| {
+        let (sin, cos) = theta.sin_cos();
+        (
+            centre + Vector::new(cos, sin) * radius,
+            Vector::new(-sin, cos),
+        )
+    };
+
+    let start = start * std::f32::consts::TAU;
+    let mut points = vec![at(start).0];
+    for i in 0..pieces {
+        let (p0, tangent0) = at(start + angle * i as f32);
+        let (p1, tangent1) = at(start + angle * (i + 1) as f32);
+        points.extend([p0 + tangent0 * reach, p1 - tangent1 * reach, p1]);
+    }
+    BezierThirdSpline::new(points)
 }
 
 impl ParametricFunction2D for CircleArc {
     fn evaluate(&self, t: T) -> Point {
+        self.evaluate_unclamped(t.value())
+    }
+
+    /// carries on round the circle
+    fn evaluate_unclamped(&self, t: f32) -> Point {
         let c = self.centre;
         let r = self.radius;
         let start_angle = self.start_angle;
         let end_angle = self.end_angle;
 
-        let theta = end_angle.value() * t.value() + (1.0 - t.value()) * start_angle.value();
+        let theta = end_angle.to_turns() * t + (1.0 - t) * start_angle.to_turns();
         (
             c.x + r * f32::cos(theta * std::f32::consts::TAU),
             c.y + r * f32::sin(theta * std::f32::consts::TAU),
@@ -58,12 +237,17 @@ impl ParametricFunction2D for CircleArc {
 
 impl ParametricFunction2D for Circle {
     fn evaluate(&self, t: T) -> Point {
+        self.evaluate_unclamped(t.value())
+    }
+
+    /// wraps round the circle
+    fn evaluate_unclamped(&self, t: f32) -> Point {
         let c = self.centre;
         let r = self.radius;
         let start_angle = self.start_angle;
         (
-            c.x + r * f32::cos((t.value() + start_angle.value()) * std::f32::consts::TAU),
-            c.y + r * f32::sin((t.value() + start_angle.value()) * std::f32::consts::TAU),
+            c.x + r * f32::cos((t + start_angle.to_turns()) * std::f32::consts::TAU),
+            c.y + r * f32::sin((t + start_angle.to_turns()) * std::f32::consts::TAU),
         )
             .into()
     }
@@ -75,6 +259,42 @@ mod tests {
     use approx::assert_relative_eq;
     use std::f32;
 
+    #[test]
+    fn test_try_into_cubics() {
+        let arc = CircleArc::with_angles(
+            (1.0, 1.0).into(),
+            2.0,
+            Some(Angle::turns(0.0)),
+            Some(Angle::turns(0.5)),
+        );
+        let spline = BezierThirdSpline::try_from(arc.clone()).unwrap();
+        assert_eq!(spline.points.len(), 7);
+        for t in T::range(9) {
+            let p = spline.evaluate(t);
+            assert_relative_eq!((p - arc.centre).length(), 2.0, epsilon = 1e-3);
+        }
+
+        let arc = CircleArc::new((0.0, 0.0).into(), -1.0, None, None);
+        assert_eq!(
+            BezierThirdSpline::try_from(arc).err(),
+            Some(ParametricsError::NonPositiveRadius { radius: -1.0 })
+        );
+    }
+
+    #[test]
+    fn test_try_new() {
+        let centre = (0.0, 0.0).into();
+        assert!(Circle::try_new(centre, 1.0, None).is_ok());
+        assert_eq!(
+            Circle::try_new(centre, 0.0, None).err(),
+            Some(ParametricsError::NonPositiveRadius { radius: 0.0 })
+        );
+        assert_eq!(
+            CircleArc::try_new(centre, 1.0, Some(Angle::turns(f32::INFINITY)), None).err(),
+            Some(ParametricsError::NotFinite)
+        );
+    }
+
     #[test]
     fn test_circle() {
         let c = Circle::new((0.0, 0.0).into(), 1.0, None);
@@ -108,4 +328,95 @@ mod tests {
         assert_relative_eq!(res.x, 0.0, epsilon = f32::EPSILON * 10.0);
         assert_relative_eq!(res.y, 1.0, epsilon = f32::EPSILON * 10.0);
     }
+
+    #[test]
+    fn test_circle_arc_through_points() {
+        // anticlockwise across the positive x-axis
+        let arc =
+            CircleArc::through_points((1.0, -1.0).into(), (2.0, 0.0).into(), (1.0, 1.0).into())
+                .unwrap();
+        assert_relative_eq!(arc.centre.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(arc.centre.y, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(arc.radius, 1.0, epsilon = 1e-5);
+
+        let res = arc.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 2.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-5);
+
+        let res = arc.end();
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+
+        // clockwise the long way round
+        let arc =
+            CircleArc::through_points((1.0, 0.0).into(), (0.0, -1.0).into(), (0.0, 1.0).into())
+                .unwrap();
+        assert_relative_eq!(
+            (arc.end_angle - arc.start_angle).to_turns(),
+            -0.75,
+            epsilon = 1e-5
+        );
+
+        let res = arc.evaluate(T::new(2.0 / 3.0));
+        assert_relative_eq!(res.x, -1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-5);
+
+        assert_eq!(
+            CircleArc::through_points((0.0, 0.0).into(), (1.0, 1.0).into(), (2.0, 2.0).into())
+                .err(),
+            Some(ParametricsError::Collinear)
+        );
+    }
+
+    #[test]
+    fn test_to_cubics() {
+        let c = Circle::new((1.0, 1.0).into(), 2.0, None);
+        let cubics = c.to_cubics(1e-2);
+        assert_eq!(cubics.points.len(), 13);
+
+        // the usual kappa for a quarter circle
+        let kappa = (cubics.points[1] - cubics.points[0]).length() / 2.0;
+        assert_relative_eq!(kappa, 0.5522848, epsilon = 1e-5);
+
+        for max_error in [1e-2, 1e-4] {
+            let cubics = c.to_cubics(max_error);
+            for p in cubics.linspace(200) {
+                let distance = (p - Point::new(1.0, 1.0)).length();
+                assert!((distance - 2.0).abs() <= max_error);
+            }
+        }
+
+        let arc = CircleArc::new(
+            (0.0, 0.0).into(),
+            1.0,
+            Some(T::new(0.5)),
+            Some(T::new(0.25)),
+        );
+        let cubics = arc.to_cubics(1e-3);
+        let res = cubics.start();
+        assert_relative_eq!(res.x, -1.0);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+        let res = cubics.end();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0);
+        let res = cubics.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, -f32::sqrt(0.5), epsilon = 1e-3);
+        assert_relative_eq!(res.y, f32::sqrt(0.5), epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_evaluate_unclamped() {
+        let c = Circle::new((0.0, 0.0).into(), 1.0, None);
+        let res = c.evaluate_unclamped(1.25);
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+        let res = c.evaluate_unclamped(-0.25);
+        assert_relative_eq!(res.y, -1.0, epsilon = 1e-5);
+
+        // the arc carries on past its end
+        let a = CircleArc::with_angles((0.0, 0.0).into(), 2.0, None, Some(Angle::turns(0.25)));
+        let res = a.evaluate_unclamped(2.0);
+        assert_relative_eq!(res.x, -2.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-5);
+    }
 }
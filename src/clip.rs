@@ -0,0 +1,205 @@
+//! Clipping curves to rectangles
+
+use euclid::{Box2D, UnknownUnit};
+
+use crate::core::{ParametricFunction2D, Point, MAX_DEPTH, T};
+
+/// Axis aligned rectangle type from Euclid, given by its minimum and maximum corners
+pub type Rect = Box2D<f32, UnknownUnit>;
+
+/// Number of pieces the search for crossings of each edge starts from, so a curve can't slip out and back in
+/// between the ends of one of them unnoticed
+const PIECES: usize = 16;
+
+/// returns the ranges of `t` over which `function` lies within `rect` (edges included), in order - see
+/// [`ParametricFunction2D::clip_to_rect`]
+pub(crate) fn clip_ranges<F: ParametricFunction2D + ?Sized>(
+    function: &F,
+    rect: Rect,
+    tolerance: f32,
+) -> Vec<(T, T)> {
+    // how far inside each edge a point is, negative when it's outside
+    let edges: [&dyn Fn(Point) -> f32; 4] = [
+        &|p| p.x - rect.min.x,
+        &|p| rect.max.x - p.x,
+        &|p| p.y - rect.min.y,
+        &|p| rect.max.y - p.y,
+    ];
+
+    let mut ts = vec![0.0, 1.0];
+    for edge in edges {
+        for i in 0..PIECES {
+            let (a, b) = (i as f32 / PIECES as f32, (i + 1) as f32 / PIECES as f32);
+            let ends = (function.evaluate(T::new(a)), function.evaluate(T::new(b)));
+            push_crossings(function, edge, (a, b), ends, tolerance, MAX_DEPTH, &mut ts);
+        }
+    }
+    ts.sort_by(f32::total_cmp);
+
+    // each stretch between neighbouring crossings is wholly in or out, so its middle says which
+    let mut ranges: Vec<(T, T)> = vec![];
+    for w in ts.windows(2) {
+        let middle = function.evaluate(T::new((w[0] + w[1]) / 2.0));
+        if !edges.iter().all(|edge| edge(middle) >= 0.0) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some((_, end)) if end.value() == w[0] => *end = T::new(w[1]),
+            _ => ranges.push((T::new(w[0]), T::new(w[1]))),
+        }
+    }
+    // just touching an edge from outside leaves nothing of any length
+    ranges.retain(|(start, end)| end.value() > start.value());
+    ranges
+}
+
+/// pushes the values of `t` in `a..b` where `function` crosses the line along `edge` - subdividing while the range
+/// is too bent to rule a crossing out, then bisecting each change of side down to `tolerance`
+fn push_crossings<F: ParametricFunction2D + ?Sized>(
+    function: &F,
+    edge: &dyn Fn(Point) -> f32,
+    (a, b): (f32, f32),
+    (pa, pb): (Point, Point),
+    tolerance: f32,
+    depth: usize,
+    crossings: &mut Vec<f32>,
+) {
+    let m = (a + b) / 2.0;
+    let pm = function.evaluate(T::new(m));
+    let bulge = (pm - pa.lerp(pb, 0.5)).length();
+    let (ga, gm, gb) = (edge(pa), edge(pm), edge(pb));
+
+    if depth == 0 || bulge <= tolerance {
+        // the middle may have poked across and back
+        if (ga >= 0.0) != (gm >= 0.0) {
+            crossings.push(bisect(function, edge, (a, m), tolerance));
+        }
+        if (gm >= 0.0) != (gb >= 0.0) {
+            crossings.push(bisect(function, edge, (m, b), tolerance));
+        }
+        return;
+    }
+
+    // the curve strays from its chord by about the bulge, so it can't reach the line from further away than that
+    let same_side = (ga >= 0.0) == (gb >= 0.0) && (ga >= 0.0) == (gm >= 0.0);
+    if same_side && ga.abs().min(gm.abs()).min(gb.abs()) > 2.0 * bulge {
+        return;
+    }
+    push_crossings(
+        function,
+        edge,
+        (a, m),
+        (pa, pm),
+        tolerance,
+        depth - 1,
+        crossings,
+    );
+    push_crossings(
+        function,
+        edge,
+        (m, b),
+        (pm, pb),
+        tolerance,
+        depth - 1,
+        crossings,
+    );
+}
+
+/// the value of `t` in `a..b` where `function` crosses the line along `edge`, on its inner side
+fn bisect<F: ParametricFunction2D + ?Sized>(
+    function: &F,
+    edge: &dyn Fn(Point) -> f32,
+    (mut a, mut b): (f32, f32),
+    tolerance: f32,
+) -> f32 {
+    let a_inside = edge(function.evaluate(T::new(a))) >= 0.0;
+    for _ in 0..32 {
+        let (pa, pb) = (function.evaluate(T::new(a)), function.evaluate(T::new(b)));
+        if (pb - pa).length() <= tolerance {
+            break;
+        }
+        let mid = (a + b) / 2.0;
+        if (edge(function.evaluate(T::new(mid))) >= 0.0) == a_inside {
+            a = mid;
+        } else {
+            b = mid;
+        }
+    }
+    if a_inside {
+        a
+    } else {
+        b
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{circle::Circle, core::Translate, segment::Segment};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_clip_to_rect() {
+        let rect = Rect::new((0.0, 0.0).into(), (2.0, 2.0).into());
+
+        let s = Segment::new((-1.0, 1.0).into(), (3.0, 1.0).into());
+        let parts = s.clip_to_rect(rect, 1e-4);
+        assert_eq!(parts.len(), 1);
+
+        let res = parts[0].start();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-4);
+        let res = parts[0].end();
+        assert_relative_eq!(res.x, 2.0, epsilon = 1e-4);
+
+        // combinators can be clipped too, sharing what they're built from
+        let moved = Translate::new(s, (0.0, 0.5).into());
+        let parts = moved.clip_to_rect(rect, 1e-4);
+        assert_eq!(parts.len(), 1);
+        assert_relative_eq!(parts[0].start().y, 1.5);
+
+        // a circle around a corner of the rectangle only has the quarter inside it
+        let c = Circle::new((2.0, 2.0).into(), 1.0, None);
+        let parts = c.clip_to_rect(rect, 1e-4);
+        assert_eq!(parts.len(), 1);
+
+        let res = parts[0].start();
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-3);
+        assert_relative_eq!(res.y, 2.0, epsilon = 1e-3);
+        let res = parts[0].end();
+        assert_relative_eq!(res.x, 2.0, epsilon = 1e-3);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_clip_to_rect_several_parts() {
+        let rect = Rect::new((0.0, 0.0).into(), (1.0, 1.0).into());
+
+        // starts inside, then falls back through the rectangle twice and rises through it twice
+        let wave = |t: T| {
+            Point::new(
+                0.5,
+                0.25 + 1.5 * f32::sin(t.value() * std::f32::consts::TAU * 2.0),
+            )
+        };
+        let parts = wave.clip_to_rect(rect, 1e-4);
+        assert_eq!(parts.len(), 5);
+        for part in parts {
+            for p in part.linspace(10) {
+                assert!(p.y >= -1e-3 && p.y <= 1.0 + 1e-3);
+            }
+        }
+
+        let outside = Segment::new((2.0, 2.0).into(), (3.0, 3.0).into());
+        assert!(outside.clip_to_rect(rect, 1e-4).is_empty());
+
+        // a circle only just too big pokes out over each edge, briefly
+        let c = Circle::new((0.5, 0.5).into(), 0.505, None);
+        let parts = c.clip_to_rect(rect, 1e-4);
+        assert_eq!(parts.len(), 4);
+        for part in parts {
+            for p in [part.start(), part.end()] {
+                let to_edge = p.x.min(p.y).min(1.0 - p.x).min(1.0 - p.y);
+                assert_relative_eq!(to_edge, 0.0, epsilon = 1e-3);
+            }
+        }
+    }
+}
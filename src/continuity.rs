@@ -0,0 +1,133 @@
+//! Checking the joins of piecewise curves for kinks and gaps
+
+use crate::core::{ParametricFunction2D, T};
+
+/// How smoothly two pieces of a curve join, from the weakest up - see [`ContinuityBreak`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Continuity {
+    /// the pieces meet
+    C0,
+    /// the pieces meet heading in the same direction
+    G1,
+    /// the pieces meet heading in the same direction at the same speed
+    C1,
+}
+
+/// A join where a piecewise curve is less smooth than it could be - the end of piece `index` and the start of the
+/// next one (the first one, for the join closing a closed curve) at `t` along the whole curve. `kind` is the weakest
+/// [`Continuity`] that fails there and `amount` how badly: the gap for [`Continuity::C0`], the angle between the
+/// tangents in "turns" for [`Continuity::G1`] and the difference between the derivatives, relative to the larger of
+/// them, for [`Continuity::C1`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContinuityBreak {
+    pub index: usize,
+    pub t: T,
+    pub kind: Continuity,
+    pub amount: f32,
+}
+
+/// checks each join between neighbouring `pieces` (and the last back to the first if `closed`), which are taken to
+/// share `t` equally, reporting any whose amount is over `tolerance`
+pub(crate) fn continuity_breaks<'a>(
+    pieces: impl IntoIterator<Item = &'a dyn ParametricFunction2D>,
+    closed: bool,
+    tolerance: f32,
+) -> Vec<ContinuityBreak> {
+    let pieces: Vec<_> = pieces.into_iter().collect();
+    let count = pieces.len();
+    let joins = if closed {
+        count
+    } else {
+        count.saturating_sub(1)
+    };
+
+    (0..joins)
+        .filter_map(|index| {
+            let (a, b) = (pieces[index], pieces[(index + 1) % count]);
+            let t = T::new((index + 1) as f32 / count as f32);
+
+            let gap = (b.start() - a.end()).length();
+            let (from, to) = (a.derivative(T::end()), b.derivative(T::start()));
+            let angle = from.angle_to(to).radians.abs() / std::f32::consts::TAU;
+            let scale = from.length().max(to.length());
+            let difference = if scale > 0.0 {
+                (to - from).length() / scale
+            } else {
+                0.0
+            };
+
+            let (kind, amount) = if gap > tolerance {
+                (Continuity::C0, gap)
+            } else if angle > tolerance {
+                (Continuity::G1, angle)
+            } else if difference > tolerance {
+                (Continuity::C1, difference)
+            } else {
+                return None;
+            };
+            Some(ContinuityBreak {
+                index,
+                t,
+                kind,
+                amount,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bezier::BezierThirdSpline,
+        core::{Concat, IntoParametric2D},
+        path::PathBuilder,
+        segment::Segment,
+    };
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_analyze_continuity() {
+        let path = PathBuilder::new()
+            .line_to((1.0, 0.0).into())
+            .line_to((2.0, 0.0).into())
+            .line_to((2.0, 1.0).into())
+            .move_to((3.0, 1.0).into())
+            .line_to((3.0, 2.0).into())
+            .build();
+        let breaks = path.analyze_continuity(1e-3);
+        assert_eq!(breaks.len(), 2);
+
+        // the corner
+        assert_eq!(breaks[0].index, 1);
+        assert_eq!(breaks[0].kind, Continuity::G1);
+        assert_relative_eq!(breaks[0].amount, 0.25, epsilon = 1e-4);
+        assert_relative_eq!(breaks[0].t.value(), 0.5);
+
+        // and the jump
+        assert_eq!(breaks[1].index, 2);
+        assert_eq!(breaks[1].kind, Continuity::C0);
+        assert_relative_eq!(breaks[1].amount, 1.0);
+
+        // straight on but speeding up
+        let concat = Concat::new(vec![
+            Segment::new((0.0, 0.0).into(), (1.0, 0.0).into()).into_parametric(),
+            Segment::new((1.0, 0.0).into(), (3.0, 0.0).into()).into_parametric(),
+        ])
+        .unwrap();
+        let breaks = concat.analyze_continuity(1e-3);
+        assert_eq!(breaks.len(), 1);
+        assert_eq!(breaks[0].kind, Continuity::C1);
+        assert_relative_eq!(breaks[0].amount, 0.5, epsilon = 1e-3);
+
+        // smooth all the way round
+        let points = [
+            (0.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (2.0, 0.0).into(),
+            (1.0, -1.0).into(),
+        ];
+        let spline = BezierThirdSpline::smooth_through_closed(&points, 0.0);
+        assert!(spline.analyze_continuity(1e-3).is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+//! Access to the control points that define a curve
+
+use crate::{
+    bezier::{BezierFourthSpline, BezierNth, BezierSecondSpline, BezierThirdSpline},
+    bspline::BSpline,
+    core::Point,
+    geometry::convex_hull,
+    polyline::{Polygon, Polyline},
+    spline::{Akima, CatmullRom, Hermite},
+};
+
+/// Curves defined by a list of control points, which can be read and moved about in place. The fixed order Beziers
+/// keep theirs in named fields, convert them to a [`BezierNth`] to use this.
+pub trait ControlPoints {
+    /// returns the control points in order
+    fn control_points(&self) -> &[Point];
+
+    /// returns the control points in order, to be moved
+    fn control_points_mut(&mut self) -> &mut [Point];
+
+    /// returns the convex hull of the control points, anticlockwise from the lowest leftmost - for Beziers and
+    /// B-splines the curve always lies within it
+    fn control_hull(&self) -> Polygon {
+        Polygon::new(convex_hull(self.control_points()))
+    }
+}
+
+impl<const N: usize> ControlPoints for BezierNth<N> {
+    fn control_points(&self) -> &[Point] {
+        &self.points
+    }
+
+    fn control_points_mut(&mut self) -> &mut [Point] {
+        &mut self.points
+    }
+}
+
+impl ControlPoints for BSpline {
+    fn control_points(&self) -> &[Point] {
+        &self.control_points
+    }
+
+    fn control_points_mut(&mut self) -> &mut [Point] {
+        &mut self.control_points
+    }
+}
+
+/// implements [`ControlPoints`] for types keeping theirs in a `points` field
+macro_rules! impl_control_points {
+    ($($t:ty),*) => {
+        $(
+            impl ControlPoints for $t {
+                fn control_points(&self) -> &[Point] {
+                    &self.points
+                }
+
+                fn control_points_mut(&mut self) -> &mut [Point] {
+                    &mut self.points
+                }
+            }
+        )*
+    };
+}
+
+impl_control_points!(
+    BezierSecondSpline,
+    BezierThirdSpline,
+    BezierFourthSpline,
+    CatmullRom,
+    Hermite,
+    Akima,
+    Polyline,
+    Polygon
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ParametricFunction2D, T};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_control_points() {
+        let mut b = BezierNth::new([
+            (0.0, 0.0).into(),
+            (1.0, 2.0).into(),
+            (2.0, 0.5).into(),
+            (3.0, 0.0).into(),
+        ]);
+        assert_eq!(b.control_points().len(), 4);
+
+        b.control_points_mut()[3] = (3.0, 3.0).into();
+        let res = b.end();
+        assert_relative_eq!(res.x, 3.0);
+        assert_relative_eq!(res.y, 3.0);
+
+        // the inner point drops out of the hull
+        let mut b = BezierNth::new([
+            (0.0, 0.0).into(),
+            (1.0, 2.0).into(),
+            (1.0, 0.5).into(),
+            (3.0, 0.0).into(),
+        ]);
+        let hull = b.control_hull();
+        assert_eq!(hull.points.len(), 3);
+        assert_relative_eq!(hull.signed_area(), 3.0);
+        assert!(b
+            .linspace(20)
+            .into_iter()
+            .all(|p| hull.contains(p) || p == b.start() || p == b.end()));
+
+        b.control_points_mut()[2] = (1.0, -1.0).into();
+        assert_eq!(b.control_hull().points.len(), 4);
+
+        let spline = CatmullRom::uniform(vec![(0.0, 0.0).into(), (1.0, 1.0).into()]);
+        assert_relative_eq!(spline.control_points()[1].x, 1.0);
+        assert!(spline.evaluate(T::new(0.5)).x > 0.0);
+    }
+}
@@ -1,12 +1,32 @@
 //! Core structs and traits
 
-use std::rc::Rc;
+use std::{
+    ops::{Add, Mul, Sub},
+    rc::Rc,
+};
 
-use euclid::{Point2D, UnknownUnit};
+use euclid::{Point2D, Point3D, Transform2D, UnknownUnit, Vector2D, Vector3D};
 use rand::prelude::*;
 
+use crate::{
+    angle::Angle,
+    arclength::{ArcLengthTable, Spacing},
+    bezier::{BezierThird, BezierThirdSpline},
+    biarc::{biarcs, ArcOrLine},
+    clip::{clip_ranges, Rect},
+    continuity::{continuity_breaks, ContinuityBreak},
+    error::{check_finite, check_points, ParametricsError},
+    random::with_default_rng,
+    segment::Segment,
+};
+
+/// Deepest the subdivision goes when fitting pieces to a curve or searching it, in
+/// [`ParametricFunction2D::approximate_with_cubics`], [`ParametricFunction2D::to_biarcs`] and
+/// [`ParametricFunction2D::clip_to_rect`]
+pub(crate) const MAX_DEPTH: usize = 12;
+
 /// The parametric value t
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct T(f32);
 
 impl T {
@@ -37,11 +57,121 @@ impl T {
     pub fn end() -> Self {
         Self(1.0)
     }
+
+    /// returns the `[T]` `s` of the way from `a` to `b`, clamped
+    pub fn lerp(a: T, b: T, s: f32) -> Self {
+        Self::new(a.0 + (b.0 - a.0) * s)
+    }
+
+    /// returns `i / n` as a `[T]`, clamped - or [`T::start`] if `n` is zero
+    pub fn from_fraction(i: usize, n: usize) -> Self {
+        if n == 0 {
+            return Self::start();
+        }
+        Self::new(i as f32 / n as f32)
+    }
+
+    /// returns `1 - t`, the same distance from the other end
+    pub fn inverse(&self) -> Self {
+        Self(1.0 - self.0)
+    }
+
+    /// returns the `n + 1` evenly spaced values from [`T::start`] to [`T::end`] inclusive - just the start if `n` is
+    /// zero
+    pub fn range(n: usize) -> TRange {
+        TRange {
+            next: 0,
+            last: n,
+            divisor: n.max(1) as f32,
+        }
+    }
+
+    /// returns the values from [`T::start`] every `step` up to [`T::end`], which is only included if a whole number of
+    /// steps lands on it - just the start if `step` isn't positive
+    pub fn stepped(step: f32) -> TRange {
+        if step <= 0.0 || !step.is_finite() {
+            return Self::range(0);
+        }
+        // a little slack so that rounding doesn't lose the end
+        let last = (1.0 / step + 1e-4).floor() as usize;
+        TRange {
+            next: 0,
+            last,
+            divisor: 1.0 / step,
+        }
+    }
+}
+
+/// Iterator over evenly spaced values of [`T`], see [`T::range`] and [`T::stepped`]
+#[derive(Clone, Debug)]
+pub struct TRange {
+    next: usize,
+    last: usize,
+    divisor: f32,
+}
+
+impl Iterator for TRange {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.next > self.last {
+            return None;
+        }
+        let t = T::new(self.next as f32 / self.divisor);
+        self.next += 1;
+        Some(t)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.last + 1).saturating_sub(self.next);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for TRange {}
+
+/// clamped, as [`T::new`]
+impl Add for T {
+    type Output = T;
+
+    fn add(self, other: T) -> T {
+        T::new(self.0 + other.0)
+    }
+}
+
+/// clamped, as [`T::new`]
+impl Sub for T {
+    type Output = T;
+
+    fn sub(self, other: T) -> T {
+        T::new(self.0 - other.0)
+    }
+}
+
+/// clamped, as [`T::new`]
+impl Mul<f32> for T {
+    type Output = T;
+
+    fn mul(self, scale: f32) -> T {
+        T::new(self.0 * scale)
+    }
 }
 
 /// Point type from Euclid
 pub type Point = Point2D<f32, UnknownUnit>;
 
+/// Vector type from Euclid
+pub type Vector = Vector2D<f32, UnknownUnit>;
+
+/// Transform type from Euclid
+pub type Transform = Transform2D<f32, UnknownUnit, UnknownUnit>;
+
+/// 3D point type from Euclid
+pub type Point3 = Point3D<f32, UnknownUnit>;
+
+/// 3D vector type from Euclid
+pub type Vector3 = Vector3D<f32, UnknownUnit>;
+
 /// 2D parametric function trait
 pub trait ParametricFunction2D {
     /// returns the value of the parametric function at the point `t`
@@ -49,13 +179,20 @@ pub trait ParametricFunction2D {
 
     /// returns `n` equally spaced points along the entire parametric function from [`T::start`] to [`T::end`]
     fn linspace(&self, n: usize) -> Vec<Point> {
-        let step_size = 1.0 / n as f32;
-        (0..=n)
-            .map(|i| {
-                let t = T::new((i as f32) * step_size);
-                self.evaluate(t)
-            })
-            .collect()
+        T::range(n).map(|t| self.evaluate(t)).collect()
+    }
+
+    /// evaluates the parametric function at `t` without clamping it into a [`T`] - beyond either end this carries on
+    /// in a straight line along the derivative there, unless the function knows better (circles wrap round, for
+    /// instance)
+    fn evaluate_unclamped(&self, t: f32) -> Point {
+        if t < 0.0 {
+            self.start() + self.derivative(T::start()) * t
+        } else if t > 1.0 {
+            self.end() + self.derivative(T::end()) * (t - 1.0)
+        } else {
+            self.evaluate(T::new(t))
+        }
     }
 
     /// returns start, or "first", point on the parametric function
@@ -68,16 +205,349 @@ pub trait ParametricFunction2D {
         self.evaluate(T::end())
     }
 
-    /// return a random point on the parametric function
+    /// return a random point on the parametric function, using the default randomness from [`crate::random`]
     fn random_point(&self) -> Point {
-        let mut rng = rand::thread_rng();
-        let t = T::new(rng.gen());
-        self.evaluate(t)
+        // the default rng is only borrowed to draw `t`, so evaluating can draw from it too
+        let t = with_default_rng(|rng| rng.gen());
+        self.evaluate(T::new(t))
     }
 
-    /// return n random points on the parametric function
+    /// return n random points on the parametric function, using the default randomness from [`crate::random`]
     fn random_points(&self, n: usize) -> Vec<Point> {
-        (0..n).map(|_| self.random_point()).collect()
+        let ts: Vec<f32> = with_default_rng(|rng| (0..n).map(|_| rng.gen()).collect());
+        ts.into_iter().map(|t| self.evaluate(T::new(t))).collect()
+    }
+
+    /// as [`ParametricFunction2D::random_point`] but drawing from `rng`
+    fn random_point_with_rng(&self, rng: &mut dyn RngCore) -> Point {
+        self.evaluate(T::new(rng.gen()))
+    }
+
+    /// as [`ParametricFunction2D::random_points`] but drawing from `rng`
+    fn random_points_with_rng(&self, n: usize, rng: &mut dyn RngCore) -> Vec<Point> {
+        (0..n).map(|_| self.random_point_with_rng(rng)).collect()
+    }
+
+    /// returns `n` random points at values of `t` drawn from `distribution` (and clamped), so they can be bunched
+    /// up towards the ends or the middle
+    fn random_points_with<D: Distribution<f32>>(
+        &self,
+        n: usize,
+        distribution: D,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Point>
+    where
+        Self: Sized,
+    {
+        distribution
+            .sample_iter(rng)
+            .take(n)
+            .map(|t| self.evaluate(T::new(t)))
+            .collect()
+    }
+
+    /// returns `n` random points spread evenly by distance along the parametric function, rather than by `t` as
+    /// [`ParametricFunction2D::random_points`] does - so the short pieces of a [`Concat`] don't get more than their
+    /// share
+    fn random_points_uniform_arclength(&self, n: usize, rng: &mut dyn RngCore) -> Vec<Point> {
+        let table = ArcLengthTable::new(self, 1e-4);
+        (0..n)
+            .map(|_| self.evaluate(table.t_at_fraction(T::new(rng.gen()))))
+            .collect()
+    }
+
+    /// returns the derivative of the parametric function with respect to `t` at `t` - by second order finite
+    /// differences, which are one sided near the start and end
+    fn derivative(&self, t: T) -> Vector {
+        let h = 1e-3;
+        let t = t.value();
+        let at = |s: f32| self.evaluate(T::new(s)).to_vector();
+
+        if t - h < 0.0 {
+            (at(t) * -3.0 + at(t + h) * 4.0 - at(t + 2.0 * h)) / (2.0 * h)
+        } else if t + h > 1.0 {
+            (at(t) * 3.0 - at(t - h) * 4.0 + at(t - 2.0 * h)) / (2.0 * h)
+        } else {
+            (at(t + h) - at(t - h)) / (2.0 * h)
+        }
+    }
+
+    /// returns the unit tangent to the parametric function at `t`, or zero where the derivative vanishes
+    fn tangent(&self, t: T) -> Vector {
+        self.derivative(t)
+            .try_normalize()
+            .unwrap_or_else(Vector::zero)
+    }
+
+    /// returns the unit normal at `t` - the [`ParametricFunction2D::tangent`] turned a quarter turn anticlockwise,
+    /// so it points to the left of the direction of travel
+    fn normal(&self, t: T) -> Vector {
+        let tangent = self.tangent(t);
+        Vector::new(-tangent.y, tangent.x)
+    }
+
+    /// returns the position, tangent and normal at `t` together, for placing oriented things along it
+    fn frame_at(&self, t: T) -> Frame2D {
+        let tangent = self.tangent(t);
+        Frame2D {
+            position: self.evaluate(t),
+            tangent,
+            normal: Vector::new(-tangent.y, tangent.x),
+        }
+    }
+
+    /// returns the `[T]` of the point on the parametric function closest to `point` - found by checking `n`
+    /// equally spaced samples and then refining around the closest of them
+    fn closest_t(&self, point: Point, n: usize) -> T {
+        let step_size = 1.0 / n as f32;
+        let distance = |t: f32| (self.evaluate(T::new(t)) - point).length();
+
+        let best = (0..=n)
+            .map(|i| (i as f32) * step_size)
+            .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+            .unwrap_or(0.0);
+
+        // golden section search in the neighbourhood of the best sample
+        let ratio = (5.0_f32.sqrt() - 1.0) / 2.0;
+        let mut lo = (best - step_size).max(0.0);
+        let mut hi = (best + step_size).min(1.0);
+        for _ in 0..40 {
+            let a = hi - ratio * (hi - lo);
+            let b = lo + ratio * (hi - lo);
+            if distance(a) < distance(b) {
+                hi = b;
+            } else {
+                lo = a;
+            }
+        }
+
+        let refined = (lo + hi) / 2.0;
+        if distance(refined) < distance(best) {
+            T::new(refined)
+        } else {
+            T::new(best)
+        }
+    }
+
+    /// returns the point on the parametric function closest to `point`, see [`ParametricFunction2D::closest_t`]
+    fn closest_point(&self, point: Point, n: usize) -> Point {
+        self.evaluate(self.closest_t(point, n))
+    }
+
+    /// returns the distance from `point` to the parametric function, see [`ParametricFunction2D::closest_t`]
+    fn distance_to(&self, point: Point, n: usize) -> f32 {
+        (self.closest_point(point, n) - point).length()
+    }
+
+    /// returns the length of the parametric function between `a` and `b` - found by adaptively subdividing the
+    /// range until the length of the chords changes by less than `tolerance`
+    fn arc_length_between(&self, a: T, b: T, tolerance: f32) -> f32 {
+        fn subdivide<P: ParametricFunction2D + ?Sized>(
+            f: &P,
+            (a, pa): (f32, Point),
+            (b, pb): (f32, Point),
+            tolerance: f32,
+            depth: usize,
+        ) -> f32 {
+            let m = (a + b) / 2.0;
+            let pm = f.evaluate(T::new(m));
+            let chord = (pb - pa).length();
+            let halves = (pm - pa).length() + (pb - pm).length();
+
+            // below this the difference is just rounding error
+            let noise = halves * f32::EPSILON * 4.0;
+            if depth == 0 || halves - chord <= tolerance.max(noise) {
+                return halves;
+            }
+            subdivide(f, (a, pa), (m, pm), tolerance / 2.0, depth - 1)
+                + subdivide(f, (m, pm), (b, pb), tolerance / 2.0, depth - 1)
+        }
+
+        // start from several pieces so that symmetric curves can't fool the first comparison
+        let pieces = 16;
+        let (a, b) = (a.value(), b.value());
+        let step = (b - a) / pieces as f32;
+        (0..pieces)
+            .map(|i| {
+                let start = a + step * i as f32;
+                let end = start + step;
+                let p_start = self.evaluate(T::new(start));
+                let p_end = self.evaluate(T::new(end));
+                subdivide(
+                    self,
+                    (start, p_start),
+                    (end, p_end),
+                    tolerance / pieces as f32,
+                    20,
+                )
+            })
+            .sum()
+    }
+
+    /// returns the length of the entire parametric function, see [`ParametricFunction2D::arc_length_between`]
+    fn arc_length(&self, tolerance: f32) -> f32 {
+        self.arc_length_between(T::start(), T::end(), tolerance)
+    }
+
+    /// returns evenly spaced points along the parametric function with the angle of the tangent at each, in "turns",
+    /// for stamping shapes or ticks along it. If `by_arc_length` is set the points are evenly spaced by distance
+    /// along it, and otherwise in `t` - see [`Spacing`] for how far apart.
+    fn stamps_along(&self, spacing: Spacing, by_arc_length: bool) -> Vec<(Point, f32)> {
+        let table = by_arc_length.then(|| ArcLengthTable::new(self, 1e-4));
+        let fractions: Vec<f32> = match spacing {
+            Spacing::Count(0) => vec![],
+            Spacing::Count(1) => vec![0.0],
+            Spacing::Count(n) => (0..n).map(|i| i as f32 / (n - 1) as f32).collect(),
+            Spacing::Every(step) if step <= 0.0 => vec![0.0],
+            Spacing::Every(step) => {
+                let total = table.as_ref().map_or(1.0, |table| table.total());
+                let count = (total / step + 1e-4).floor() as usize;
+                (0..=count)
+                    .map(|i| i as f32 * step / total.max(step))
+                    .collect()
+            }
+        };
+
+        fractions
+            .into_iter()
+            .map(|fraction| {
+                let t = match &table {
+                    Some(table) => table.t_at_fraction(T::new(fraction)),
+                    None => T::new(fraction),
+                };
+                let frame = self.frame_at(t);
+                (frame.position, frame.angle())
+            })
+            .collect()
+    }
+
+    /// approximates the parametric function with cubic Beziers matching its ends and derivatives, splitting it until
+    /// each piece is within `tolerance` of it - for handing procedural curves to something that only understands
+    /// Beziers, like SVG
+    fn approximate_with_cubics(&self, tolerance: f32) -> BezierThirdSpline {
+        /// pushes the control points and end point of the cubic(s) approximating the range `a..b`
+        fn push_cubics<P: ParametricFunction2D + ?Sized>(
+            f: &P,
+            (a, b): (f32, f32),
+            tolerance: f32,
+            depth: usize,
+            points: &mut Vec<Point>,
+        ) {
+            let (ta, tb) = (T::new(a), T::new(b));
+            let (start, end) = (f.evaluate(ta), f.evaluate(tb));
+            let reach = (b - a) / 3.0;
+            let control1 = start + f.derivative(ta) * reach;
+            let control2 = end - f.derivative(tb) * reach;
+
+            let cubic = BezierThird::new(start, end, control1, control2);
+            let close_enough = [0.25, 0.5, 0.75].iter().all(|&s| {
+                let expected = f.evaluate(T::new(a + (b - a) * s));
+                (cubic.evaluate(T::new(s)) - expected).length() <= tolerance
+            });
+
+            if close_enough || depth == 0 {
+                points.extend([control1, control2, end]);
+            } else {
+                let mid = (a + b) / 2.0;
+                push_cubics(f, (a, mid), tolerance, depth - 1, points);
+                push_cubics(f, (mid, b), tolerance, depth - 1, points);
+            }
+        }
+
+        let mut points = vec![self.start()];
+        push_cubics(self, (0.0, 1.0), tolerance, MAX_DEPTH, &mut points);
+        BezierThirdSpline::new(points)
+    }
+
+    /// approximates the parametric function with biarcs - pairs of circular arcs (or lines, where they're flat)
+    /// meeting with a shared tangent - splitting it until each is within `tolerance` of it. This is what CNC
+    /// controllers and laser cutters want rather than lots of tiny lines.
+    fn to_biarcs(&self, tolerance: f32) -> Vec<ArcOrLine> {
+        biarcs(self, tolerance)
+    }
+
+    /// returns the parts of the parametric function that lie within `rect` (edges included), in order, each as a
+    /// [`SubCurve`] of a copy of it. Crossings of each edge are found by subdividing wherever the curve bends close
+    /// enough to reach it and then bisecting until the ends of each part are within `tolerance` of the edge.
+    fn clip_to_rect(&self, rect: Rect, tolerance: f32) -> Vec<SubCurve>
+    where
+        Self: Sized + Clone + 'static,
+    {
+        let function = self.clone().into_parametric();
+        clip_ranges(self, rect, tolerance)
+            .into_iter()
+            .map(|(start, end)| SubCurve::new(function.clone(), start, end))
+            .collect()
+    }
+
+    /// returns a short readable description of the parametric function, e.g.
+    /// `Rotate(Concat[Segment, BezierThird], 0.25turn)` - combinators describe what they're built from, everything
+    /// else is just its type name
+    fn describe(&self) -> String {
+        short_type_name(std::any::type_name::<Self>())
+    }
+
+    /// returns the transform and the function it applies to, if this is one of the affine combinators - with any
+    /// directly nested affine combinators folded into the transform, see [`compile`]
+    fn affine_parts(&self) -> Option<(Transform, Rc<Box<dyn ParametricFunction2D>>)> {
+        None
+    }
+
+    /// returns an equivalent parametric function with each chain of affine combinators collapsed into a single
+    /// [`Affine`], or `None` if there is nothing to collapse, see [`compile`]
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        None
+    }
+}
+
+/// strips the module paths from a type name, leaving e.g. `BezierNth<4>` - closures are described as `Fn`
+pub(crate) fn short_type_name(name: &str) -> String {
+    let mut short = String::new();
+    let mut segment = String::new();
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            segment.clear();
+        } else if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else {
+            short.push_str(&segment);
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(&segment);
+    short.replace("{{closure}}", "Fn")
+}
+
+/// joins up the descriptions of `functions` with commas
+fn describe_all(functions: &[Rc<Box<dyn ParametricFunction2D>>]) -> String {
+    functions
+        .iter()
+        .map(|function| function.describe())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A point on a curve with its unit tangent and normal there, see [`ParametricFunction2D::frame_at`] - the tangent
+/// and normal are both zero where the curve stops
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame2D {
+    pub position: Point,
+    pub tangent: Vector,
+    pub normal: Vector,
+}
+
+impl Frame2D {
+    /// returns the angle of the tangent from the positive x-axis, in "turns"
+    pub fn angle(&self) -> f32 {
+        self.tangent.y.atan2(self.tangent.x) / std::f32::consts::TAU
+    }
+
+    /// returns `local`, given with x along the tangent and y along the normal, in the curve's coordinates
+    pub fn to_world(&self, local: Point) -> Point {
+        self.position + self.tangent * local.x + self.normal * local.y
     }
 }
 
@@ -88,13 +558,7 @@ pub trait ParametricFunction1D {
 
     /// returns `n` equally spaced points along the entire parametric function from [`T::start`] to [`T::end`]
     fn linspace(&self, n: usize) -> Vec<f32> {
-        let step_size = 1.0 / n as f32;
-        (0..=n)
-            .map(|i| {
-                let t = T::new((i as f32) * step_size);
-                self.evaluate(t)
-            })
-            .collect()
+        T::range(n).map(|t| self.evaluate(t)).collect()
     }
 
     /// returns start, or "first", point on the parametric function
@@ -107,26 +571,307 @@ pub trait ParametricFunction1D {
         self.evaluate(T::end())
     }
 
-    /// return a random point on the parametric function
+    /// return a random point on the parametric function, using the default randomness from [`crate::random`]
     fn random_point(&self) -> f32 {
-        let mut rng = rand::thread_rng();
-        let t = T::new(rng.gen());
-        self.evaluate(t)
+        // the default rng is only borrowed to draw `t`, so evaluating can draw from it too
+        let t = with_default_rng(|rng| rng.gen());
+        self.evaluate(T::new(t))
     }
 
-    /// return n random points on the parametric function
+    /// return n random points on the parametric function, using the default randomness from [`crate::random`]
     fn random_points(&self, n: usize) -> Vec<f32> {
-        (0..n).map(|_| self.random_point()).collect()
+        let ts: Vec<f32> = with_default_rng(|rng| (0..n).map(|_| rng.gen()).collect());
+        ts.into_iter().map(|t| self.evaluate(T::new(t))).collect()
+    }
+
+    /// as [`ParametricFunction1D::random_point`] but drawing from `rng`
+    fn random_point_with_rng(&self, rng: &mut dyn RngCore) -> f32 {
+        self.evaluate(T::new(rng.gen()))
+    }
+
+    /// as [`ParametricFunction1D::random_points`] but drawing from `rng`
+    fn random_points_with_rng(&self, n: usize, rng: &mut dyn RngCore) -> Vec<f32> {
+        (0..n).map(|_| self.random_point_with_rng(rng)).collect()
+    }
+
+    /// returns the derivative of the parametric function with respect to `t` at `t`, by finite differences as
+    /// [`ParametricFunction2D::derivative`]
+    fn derivative(&self, t: T) -> f32 {
+        let h = 1e-3;
+        let t = t.value();
+        let at = |s: f32| self.evaluate(T::new(s));
+
+        if t - h < 0.0 {
+            (at(t) * -3.0 + at(t + h) * 4.0 - at(t + 2.0 * h)) / (2.0 * h)
+        } else if t + h > 1.0 {
+            (at(t) * 3.0 - at(t - h) * 4.0 + at(t - 2.0 * h)) / (2.0 * h)
+        } else {
+            (at(t + h) - at(t - h)) / (2.0 * h)
+        }
+    }
+
+    /// returns the integral of the parametric function from `a` to `b` (negative if `b` comes before `a`), by
+    /// adaptive Simpson's rule - so a speed profile integrates to the distance travelled
+    fn integral(&self, a: T, b: T) -> f32 {
+        let f = |s: f32| self.evaluate(T::new(s));
+        let (a, b) = (a.value(), b.value());
+        let (fa, fb, fm) = (f(a), f(b), f((a + b) / 2.0));
+        let whole = (b - a) / 6.0 * (fa + 4.0 * fm + fb);
+        simpson(&f, (a, b), (fa, fm, fb), whole, 1e-6, 20)
+    }
+}
+
+/// 3D parametric function trait
+pub trait ParametricFunction3D {
+    /// returns the value of the parametric function at the point `t`
+    fn evaluate(&self, t: T) -> Point3;
+
+    /// returns `n` equally spaced points along the entire parametric function from [`T::start`] to [`T::end`]
+    fn linspace(&self, n: usize) -> Vec<Point3> {
+        T::range(n).map(|t| self.evaluate(t)).collect()
+    }
+
+    /// returns start, or "first", point on the parametric function
+    fn start(&self) -> Point3 {
+        self.evaluate(T::start())
+    }
+
+    /// returns end, or"last", point on the parametric function
+    fn end(&self) -> Point3 {
+        self.evaluate(T::end())
+    }
+
+    /// returns the derivative of the parametric function with respect to `t` at `t`, see
+    /// [`ParametricFunction2D::derivative`]
+    fn derivative(&self, t: T) -> Vector3 {
+        let h = 1e-3;
+        let t = t.value();
+        let at = |s: f32| self.evaluate(T::new(s)).to_vector();
+
+        if t - h < 0.0 {
+            (at(t) * -3.0 + at(t + h) * 4.0 - at(t + 2.0 * h)) / (2.0 * h)
+        } else if t + h > 1.0 {
+            (at(t) * 3.0 - at(t - h) * 4.0 + at(t - 2.0 * h)) / (2.0 * h)
+        } else {
+            (at(t + h) - at(t - h)) / (2.0 * h)
+        }
+    }
+
+    /// returns the unit tangent to the parametric function at `t`, or zero where the derivative vanishes
+    fn tangent(&self, t: T) -> Vector3 {
+        self.derivative(t)
+            .try_normalize()
+            .unwrap_or_else(Vector3::zero)
+    }
+
+    /// returns the length of the parametric function, summing up `n` straight lines along it
+    fn arc_length(&self, n: usize) -> f32 {
+        self.linspace(n.max(2))
+            .windows(2)
+            .map(|w| (w[1] - w[0]).length())
+            .sum()
     }
 }
 
-/// The concatenation of multiple things that implement [`ParametricFunction2D`]
+/// adaptive Simpson's rule over `a..b`, given the values at the ends and middle and the estimate over the whole
+fn simpson(
+    f: &dyn Fn(f32) -> f32,
+    (a, b): (f32, f32),
+    (fa, fm, fb): (f32, f32, f32),
+    whole: f32,
+    tolerance: f32,
+    depth: usize,
+) -> f32 {
+    let m = (a + b) / 2.0;
+    let (left_m, right_m) = ((a + m) / 2.0, (m + b) / 2.0);
+    let (f_left, f_right) = (f(left_m), f(right_m));
+    let left = (m - a) / 6.0 * (fa + 4.0 * f_left + fm);
+    let right = (b - m) / 6.0 * (fm + 4.0 * f_right + fb);
+
+    let error = left + right - whole;
+    if depth == 0 || error.abs() <= 15.0 * tolerance {
+        return left + right + error / 15.0;
+    }
+    simpson(
+        f,
+        (a, m),
+        (fa, f_left, fm),
+        left,
+        tolerance / 2.0,
+        depth - 1,
+    ) + simpson(
+        f,
+        (m, b),
+        (fm, f_right, fb),
+        right,
+        tolerance / 2.0,
+        depth - 1,
+    )
+}
+
+/// The shared form of a curve which the combinators hold
+pub type DynCurve = Rc<Box<dyn ParametricFunction2D>>;
+
+/// returns `function` in the shared form, see [`DynCurve`]
+pub fn boxed(function: impl ParametricFunction2D + 'static) -> DynCurve {
+    Rc::new(Box::new(function))
+}
+
+/// Conversion into the shared form, [`DynCurve`], which the combinators hold
+pub trait IntoParametric2D {
+    fn into_parametric(self) -> DynCurve;
+}
+
+impl<P> IntoParametric2D for P
+where
+    P: ParametricFunction2D + 'static,
+{
+    fn into_parametric(self) -> DynCurve {
+        boxed(self)
+    }
+}
+
+impl IntoParametric2D for DynCurve {
+    fn into_parametric(self) -> DynCurve {
+        self
+    }
+}
+
+/// The concatenation of multiple things that implement [`ParametricFunction2D`] - it may be empty, and then gives
+/// the origin everywhere, which is where one built up with [`Concat::push`] or by collecting from an iterator starts
+#[derive(Clone)]
 pub struct Concat {
     pub functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
 }
 
+impl Concat {
+    /// fails if there are no `functions` - an empty [`Concat`] is only the origin, so passing one in here is taken to
+    /// be a mistake
+    pub fn new(
+        functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
+    ) -> Result<Self, ParametricsError> {
+        if functions.is_empty() {
+            return Err(ParametricsError::Empty);
+        }
+        Ok(Self { functions })
+    }
+
+    /// as [`Concat::new`] but taking the functions from an iterator, e.g. `Concat::from_curves([boxed(a), boxed(b)])`
+    pub fn from_curves(
+        curves: impl IntoIterator<Item = DynCurve>,
+    ) -> Result<Self, ParametricsError> {
+        Self::new(curves.into_iter().collect())
+    }
+
+    /// as [`Concat::new`] but also fails if the end of any of the `functions` is further than `tolerance` from the
+    /// start of the next one - the error reports the first such join
+    pub fn new_continuous(
+        functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
+        tolerance: f32,
+    ) -> Result<Self, ParametricsError> {
+        let concat = Self::new(functions)?;
+        for (index, pair) in concat.functions.windows(2).enumerate() {
+            let gap = (pair[1].start() - pair[0].end()).length();
+            if gap > tolerance {
+                return Err(ParametricsError::Discontinuous { index, gap });
+            }
+        }
+        Ok(concat)
+    }
+
+    /// as [`Concat::new`] but with the joins cleaned up - where the end of one of the `functions` is within
+    /// `tolerance` of the start of the next, both are nudged to meet halfway, and if `bridge` is set any wider gaps
+    /// are filled in with a [`Segment`] (which takes its own share of `t`)
+    pub fn welded(
+        functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
+        tolerance: f32,
+        bridge: bool,
+    ) -> Result<Self, ParametricsError> {
+        if functions.is_empty() {
+            return Err(ParametricsError::Empty);
+        }
+
+        let count = functions.len();
+        let mut offsets = vec![(Vector::zero(), Vector::zero()); count];
+        let mut bridges = vec![None; count];
+        for index in 0..count - 1 {
+            let (end, start) = (functions[index].end(), functions[index + 1].start());
+            let gap = start - end;
+            if gap.length() <= tolerance {
+                offsets[index].1 = gap / 2.0;
+                offsets[index + 1].0 = -gap / 2.0;
+            } else if bridge {
+                bridges[index] = Some((end, start));
+            }
+        }
+
+        let mut welded = vec![];
+        for ((function, (start, end)), bridge) in functions.into_iter().zip(offsets).zip(bridges) {
+            if start == Vector::zero() && end == Vector::zero() {
+                welded.push(function);
+            } else {
+                welded.push(
+                    Nudge {
+                        function,
+                        start,
+                        end,
+                    }
+                    .into_parametric(),
+                );
+            }
+            if let Some((from, to)) = bridge {
+                welded.push(Segment::new(from, to).into_parametric());
+            }
+        }
+        Ok(Self { functions: welded })
+    }
+
+    /// a [`WeightedConcat`] where each of the `functions` gets a share of `t` proportional to its arc length, so
+    /// that the speed of traversal carries on across the joins
+    pub fn by_arc_length(
+        functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
+        tolerance: f32,
+    ) -> WeightedConcat {
+        let weights = functions.iter().map(|f| f.arc_length(tolerance)).collect();
+        WeightedConcat { functions, weights }
+    }
+
+    /// returns the joins between the functions that aren't smooth to within `tolerance`, see [`ContinuityBreak`]
+    pub fn analyze_continuity(&self, tolerance: f32) -> Vec<ContinuityBreak> {
+        continuity_breaks(
+            self.functions.iter().map(|f| f.as_ref().as_ref()),
+            false,
+            tolerance,
+        )
+    }
+
+    /// adds `function` to the end, taking its own share of `t`
+    pub fn push(&mut self, function: impl IntoParametric2D) {
+        self.functions.push(function.into_parametric());
+    }
+}
+
+impl<F: IntoParametric2D> FromIterator<F> for Concat {
+    fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Self {
+        Self {
+            functions: iter.into_iter().map(F::into_parametric).collect(),
+        }
+    }
+}
+
+impl<F: IntoParametric2D> Extend<F> for Concat {
+    fn extend<I: IntoIterator<Item = F>>(&mut self, iter: I) {
+        self.functions
+            .extend(iter.into_iter().map(F::into_parametric));
+    }
+}
+
 impl ParametricFunction2D for Concat {
     fn evaluate(&self, t: T) -> Point {
+        if self.functions.is_empty() {
+            return Point::origin();
+        }
+
         if t == T::start() {
             return self.functions[0].evaluate(t);
         }
@@ -145,71 +890,377 @@ impl ParametricFunction2D for Concat {
 
         self.functions[index].evaluate(interp_t)
     }
-}
 
-/// The repetition `n` times of a thing that implements [`ParametricFunction2D`]
-pub struct Repeat {
-    pub function: Rc<Box<dyn ParametricFunction2D>>,
-    pub n: usize,
-}
-impl ParametricFunction2D for Repeat {
-    fn evaluate(&self, t: T) -> Point {
-        let functions = (0..self.n).map(|_| self.function.clone()).collect();
-        let concat = Concat { functions };
-        concat.evaluate(t)
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        let functions = optimize_all(&self.functions)?;
+        Some(Rc::new(Box::new(Concat { functions })))
+    }
+
+    fn describe(&self) -> String {
+        format!("Concat[{}]", describe_all(&self.functions))
     }
 }
-/// The rotation around `centre` by `angle` (in "turns") of a thing that implements [`ParametricFunction2D`]
-pub struct Rotate {
-    pub function: Rc<Box<dyn ParametricFunction2D>>,
-    pub centre: Point,
-    pub angle: T,
+
+/// `function` moved by `start` at its start, `end` at its end and in proportion in between, see [`Concat::welded`]
+struct Nudge {
+    function: Rc<Box<dyn ParametricFunction2D>>,
+    start: Vector,
+    end: Vector,
 }
-impl ParametricFunction2D for Rotate {
+
+impl ParametricFunction2D for Nudge {
     fn evaluate(&self, t: T) -> Point {
-        let val = self.function.evaluate(t);
+        self.function.evaluate(t) + self.start.lerp(self.end, t.value())
+    }
 
-        (
-            self.centre.x
-                + (val.x - self.centre.x) * f32::cos(self.angle.value() * std::f32::consts::TAU)
-                - (val.y - self.centre.y) * f32::sin(self.angle.value() * std::f32::consts::TAU),
-            self.centre.y
-                + (val.x - self.centre.x) * f32::sin(self.angle.value() * std::f32::consts::TAU)
-                + (val.y - self.centre.y) * f32::cos(self.angle.value() * std::f32::consts::TAU),
-        )
-            .into()
+    fn describe(&self) -> String {
+        self.function.describe()
     }
 }
 
-/// The translation by `by` of a thing that implements [`ParametricFunction2D`]
-pub struct Translate {
-    pub function: Rc<Box<dyn ParametricFunction2D>>,
-    pub by: Point,
+/// The concatenation of multiple things that implement [`ParametricFunction2D`] where each one gets a share of `t`
+/// proportional to its entry in `weights`, rather than the equal shares of [`Concat`] - and like an empty [`Concat`],
+/// an empty one gives the origin
+#[derive(Clone)]
+pub struct WeightedConcat {
+    pub functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
+    pub weights: Vec<f32>,
 }
 
-impl ParametricFunction2D for Translate {
-    fn evaluate(&self, t: T) -> Point {
-        let val = self.function.evaluate(t);
-        (val.x + self.by.x, val.y + self.by.y).into()
+impl WeightedConcat {
+    pub fn new(functions: Vec<Rc<Box<dyn ParametricFunction2D>>>, weights: Vec<f32>) -> Self {
+        Self { functions, weights }
     }
-}
 
-/// Combination of [`Rotate`] and [`Translate`]
-pub struct RotateTranslate {
-    pub function: Rc<Box<dyn ParametricFunction2D>>,
-    pub by: Point,
-    pub centre: Point,
-    pub angle: T,
-    pub rotate_first: bool,
+    /// as [`WeightedConcat::new`] but fails if there are no functions, the weights don't go one to one with them,
+    /// or any weight is negative or not finite or none are above zero
+    pub fn try_new(
+        functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
+        weights: Vec<f32>,
+    ) -> Result<Self, ParametricsError> {
+        if functions.is_empty() {
+            return Err(ParametricsError::Empty);
+        }
+        if weights.len() != functions.len() {
+            return Err(ParametricsError::LengthMismatch {
+                expected: functions.len(),
+                found: weights.len(),
+            });
+        }
+        check_finite(weights.iter().copied())?;
+        if weights.iter().any(|&w| w < 0.0) || !weights.iter().any(|&w| w > 0.0) {
+            return Err(ParametricsError::InvalidWeights);
+        }
+        Ok(Self::new(functions, weights))
+    }
 }
 
-impl ParametricFunction2D for RotateTranslate {
+impl ParametricFunction2D for WeightedConcat {
     fn evaluate(&self, t: T) -> Point {
-        if self.rotate_first {
-            let r = Rotate {
-                function: self.function.clone(),
-                centre: self.centre,
-                angle: self.angle,
+        if self.functions.is_empty() {
+            return Point::origin();
+        }
+
+        if t == T::start() {
+            return self.functions[0].evaluate(t);
+        }
+
+        if t == T::end() {
+            return self.functions[self.functions.len() - 1].evaluate(t);
+        }
+
+        let total: f32 = self.weights.iter().filter(|&&w| w > 0.0).sum();
+        let target = t.value() * total;
+
+        let mut cumulative = 0.0;
+        for (function, &weight) in self.functions.iter().zip(&self.weights) {
+            if weight <= 0.0 {
+                continue;
+            }
+            if target < cumulative + weight {
+                return function.evaluate(T::new((target - cumulative) / weight));
+            }
+            cumulative += weight;
+        }
+
+        self.functions[self.functions.len() - 1].evaluate(T::end())
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        let functions = optimize_all(&self.functions)?;
+        Some(Rc::new(Box::new(WeightedConcat {
+            functions,
+            weights: self.weights.clone(),
+        })))
+    }
+
+    fn describe(&self) -> String {
+        let pieces: Vec<_> = self
+            .functions
+            .iter()
+            .zip(&self.weights)
+            .map(|(function, weight)| format!("{}: {}", function.describe(), weight))
+            .collect();
+        format!("WeightedConcat[{}]", pieces.join(", "))
+    }
+}
+
+/// returns which of `count` equal pieces `t` falls in and how far along it, splitting it up as a [`Concat`] would -
+/// `None` if there are no pieces
+pub(crate) fn locate(count: usize, t: T) -> Option<(usize, T)> {
+    if count == 0 {
+        return None;
+    }
+    let position = t.value() * count as f32;
+    let index = (position.floor() as usize).min(count - 1);
+    Some((index, T::new(position - index as f32)))
+}
+
+/// The repetition `n` times of a thing that implements [`ParametricFunction2D`] - see [`PingPong`] for going back
+/// and forth along it instead
+#[derive(Clone)]
+pub struct Repeat {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub n: usize,
+}
+
+impl Repeat {
+    pub fn new(function: impl IntoParametric2D, n: usize) -> Self {
+        Self {
+            function: function.into_parametric(),
+            n,
+        }
+    }
+
+    /// as [`Repeat::new`] but fails if `n` is zero
+    pub fn try_new(function: impl IntoParametric2D, n: usize) -> Result<Self, ParametricsError> {
+        if n == 0 {
+            return Err(ParametricsError::ZeroCount);
+        }
+        Ok(Self::new(function, n))
+    }
+}
+
+impl ParametricFunction2D for Repeat {
+    fn evaluate(&self, t: T) -> Point {
+        repeated(&**self.function, self.n, false, t)
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(Repeat {
+            function: self.function.optimize()?,
+            n: self.n,
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("Repeat({}, {})", self.function.describe(), self.n)
+    }
+}
+
+/// evaluates `function` repeated `n` times (at least once) at `t`, with every other repetition traversed backwards if
+/// `mirror` is set - shared by [`Repeat`] and [`PingPong`]
+fn repeated(function: &dyn ParametricFunction2D, n: usize, mirror: bool, t: T) -> Point {
+    let (i, t) = locate(n.max(1), t).unwrap_or((0, t));
+    if mirror && i % 2 == 1 {
+        function.evaluate(T::new(1.0 - t.value()))
+    } else {
+        function.evaluate(t)
+    }
+}
+
+/// The repetition `n` times of a thing that implements [`ParametricFunction2D`], going back and forth along it
+/// rather than jumping back to its start each time
+#[derive(Clone)]
+pub struct PingPong {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub n: usize,
+}
+
+impl PingPong {
+    pub fn new(function: impl IntoParametric2D, n: usize) -> Self {
+        Self {
+            function: function.into_parametric(),
+            n,
+        }
+    }
+
+    /// as [`PingPong::new`] but fails if `n` is zero
+    pub fn try_new(function: impl IntoParametric2D, n: usize) -> Result<Self, ParametricsError> {
+        if n == 0 {
+            return Err(ParametricsError::ZeroCount);
+        }
+        Ok(Self::new(function, n))
+    }
+}
+
+impl ParametricFunction2D for PingPong {
+    fn evaluate(&self, t: T) -> Point {
+        repeated(&**self.function, self.n, true, t)
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(PingPong {
+            function: self.function.optimize()?,
+            n: self.n,
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("PingPong({}, {})", self.function.describe(), self.n)
+    }
+}
+
+/// The rotation around `centre` by `angle` (in "turns") of a thing that implements [`ParametricFunction2D`]
+#[derive(Clone)]
+pub struct Rotate {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub centre: Point,
+    pub angle: Angle,
+}
+
+impl Rotate {
+    pub fn new(function: impl IntoParametric2D, centre: Point, angle: impl Into<Angle>) -> Self {
+        Self {
+            function: function.into_parametric(),
+            centre,
+            angle: angle.into(),
+        }
+    }
+
+    /// returns the rotation as a [`Transform`]
+    pub fn transform(&self) -> Transform {
+        Transform::translation(-self.centre.x, -self.centre.y)
+            .then_rotate(euclid::Angle::radians(self.angle.to_radians()))
+            .then_translate(self.centre.to_vector())
+    }
+}
+
+impl ParametricFunction2D for Rotate {
+    fn evaluate(&self, t: T) -> Point {
+        let val = self.function.evaluate(t);
+
+        (
+            self.centre.x + (val.x - self.centre.x) * f32::cos(self.angle.to_radians())
+                - (val.y - self.centre.y) * f32::sin(self.angle.to_radians()),
+            self.centre.y
+                + (val.x - self.centre.x) * f32::sin(self.angle.to_radians())
+                + (val.y - self.centre.y) * f32::cos(self.angle.to_radians()),
+        )
+            .into()
+    }
+
+    fn affine_parts(&self) -> Option<(Transform, Rc<Box<dyn ParametricFunction2D>>)> {
+        Some(chain_affine(self.transform(), &self.function))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        optimize_affine(self)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Rotate({}, {}turn)",
+            self.function.describe(),
+            self.angle.to_turns()
+        )
+    }
+}
+
+/// The translation by `by` of a thing that implements [`ParametricFunction2D`]
+#[derive(Clone)]
+pub struct Translate {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub by: Point,
+}
+
+impl Translate {
+    pub fn new(function: impl IntoParametric2D, by: Point) -> Self {
+        Self {
+            function: function.into_parametric(),
+            by,
+        }
+    }
+
+    /// returns the translation as a [`Transform`]
+    pub fn transform(&self) -> Transform {
+        Transform::translation(self.by.x, self.by.y)
+    }
+}
+
+impl ParametricFunction2D for Translate {
+    fn evaluate(&self, t: T) -> Point {
+        let val = self.function.evaluate(t);
+        (val.x + self.by.x, val.y + self.by.y).into()
+    }
+
+    fn affine_parts(&self) -> Option<(Transform, Rc<Box<dyn ParametricFunction2D>>)> {
+        Some(chain_affine(self.transform(), &self.function))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        optimize_affine(self)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Translate({}, ({}, {}))",
+            self.function.describe(),
+            self.by.x,
+            self.by.y
+        )
+    }
+}
+
+/// Combination of [`Rotate`] and [`Translate`]
+#[derive(Clone)]
+pub struct RotateTranslate {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub by: Point,
+    pub centre: Point,
+    pub angle: Angle,
+    pub rotate_first: bool,
+}
+
+impl RotateTranslate {
+    pub fn new(
+        function: impl IntoParametric2D,
+        by: Point,
+        centre: Point,
+        angle: impl Into<Angle>,
+        rotate_first: bool,
+    ) -> Self {
+        Self {
+            function: function.into_parametric(),
+            by,
+            centre,
+            angle: angle.into(),
+            rotate_first,
+        }
+    }
+
+    /// returns the combined rotation and translation as a [`Transform`]
+    pub fn transform(&self) -> Transform {
+        let rotation = Transform::translation(-self.centre.x, -self.centre.y)
+            .then_rotate(euclid::Angle::radians(self.angle.to_radians()))
+            .then_translate(self.centre.to_vector());
+        let translation = Transform::translation(self.by.x, self.by.y);
+
+        if self.rotate_first {
+            rotation.then(&translation)
+        } else {
+            translation.then(&rotation)
+        }
+    }
+}
+
+impl ParametricFunction2D for RotateTranslate {
+    fn evaluate(&self, t: T) -> Point {
+        if self.rotate_first {
+            let r = Rotate {
+                function: self.function.clone(),
+                centre: self.centre,
+                angle: self.angle,
             };
             let tr = Translate {
                 function: Rc::new(Box::new(r)),
@@ -229,6 +1280,24 @@ impl ParametricFunction2D for RotateTranslate {
             r.evaluate(t)
         }
     }
+
+    fn affine_parts(&self) -> Option<(Transform, Rc<Box<dyn ParametricFunction2D>>)> {
+        Some(chain_affine(self.transform(), &self.function))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        optimize_affine(self)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "RotateTranslate({}, ({}, {}), {}turn)",
+            self.function.describe(),
+            self.by.x,
+            self.by.y,
+            self.angle.to_turns()
+        )
+    }
 }
 
 impl<F> ParametricFunction2D for F
@@ -249,6 +1318,15 @@ where
     }
 }
 
+impl<F> ParametricFunction3D for F
+where
+    F: Fn(T) -> Point3,
+{
+    fn evaluate(&self, t: T) -> Point3 {
+        self(t)
+    }
+}
+
 impl<F, G> ParametricFunction2D for (F, G)
 where
     F: ParametricFunction1D,
@@ -259,30 +1337,692 @@ where
     }
 }
 
-pub struct Scale {
-    pub function: Rc<Box<dyn ParametricFunction2D>>,
-    pub centre: Point,
-    pub scale_x: f32,
-    pub scale_y: f32,
+/// One of the coordinate axes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+/// One coordinate of a thing that implements [`ParametricFunction2D`], as a [`ParametricFunction1D`] - so it can be
+/// integrated, used as an easing and so on
+pub struct Component {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub axis: Axis,
+}
+
+impl Component {
+    pub fn new(function: impl IntoParametric2D, axis: Axis) -> Self {
+        Self {
+            function: function.into_parametric(),
+            axis,
+        }
+    }
+}
+
+impl ParametricFunction1D for Component {
+    fn evaluate(&self, t: T) -> f32 {
+        let point = self.function.evaluate(t);
+        match self.axis {
+            Axis::X => point.x,
+            Axis::Y => point.y,
+        }
+    }
+}
+
+/// Two things that implement [`ParametricFunction1D`] as the x and y coordinates of a curve, as the `(F, G)` tuple
+/// is, but with each evaluated at its own remapping of `t` - `warp_x(t)` and `warp_y(t)`, clamped into a [`T`] - so
+/// reusable parts can be phase shifted or sped up independently
+pub struct Zip {
+    pub x: Box<dyn ParametricFunction1D>,
+    pub y: Box<dyn ParametricFunction1D>,
+    pub warp_x: Box<dyn ParametricFunction1D>,
+    pub warp_y: Box<dyn ParametricFunction1D>,
+}
+
+impl Zip {
+    /// the coordinates both evaluated at `t` itself
+    pub fn new(
+        x: impl ParametricFunction1D + 'static,
+        y: impl ParametricFunction1D + 'static,
+    ) -> Self {
+        Self::warped(x, y, |t: T| t.value(), |t: T| t.value())
+    }
+
+    /// each coordinate evaluated at its own warp of `t`
+    pub fn warped(
+        x: impl ParametricFunction1D + 'static,
+        y: impl ParametricFunction1D + 'static,
+        warp_x: impl ParametricFunction1D + 'static,
+        warp_y: impl ParametricFunction1D + 'static,
+    ) -> Self {
+        Self {
+            x: Box::new(x),
+            y: Box::new(y),
+            warp_x: Box::new(warp_x),
+            warp_y: Box::new(warp_y),
+        }
+    }
+}
+
+impl ParametricFunction2D for Zip {
+    fn evaluate(&self, t: T) -> Point {
+        let x = self.x.evaluate(T::new(self.warp_x.evaluate(t)));
+        let y = self.y.evaluate(T::new(self.warp_y.evaluate(t)));
+        (x, y).into()
+    }
+
+    fn describe(&self) -> String {
+        // the coordinates are things that implement `ParametricFunction1D`, which can't describe themselves
+        "Zip".to_string()
+    }
+}
+
+#[derive(Clone)]
+pub struct Scale {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub centre: Point,
+    pub scale_x: f32,
+    pub scale_y: f32,
+}
+
+impl Scale {
+    pub fn new(function: impl IntoParametric2D, centre: Point, scale_x: f32, scale_y: f32) -> Self {
+        Self {
+            function: function.into_parametric(),
+            centre,
+            scale_x,
+            scale_y,
+        }
+    }
+
+    /// as [`Scale::new`] but fails if the centre or either scale isn't finite
+    pub fn try_new(
+        function: impl IntoParametric2D,
+        centre: Point,
+        scale_x: f32,
+        scale_y: f32,
+    ) -> Result<Self, ParametricsError> {
+        check_points(&[centre])?;
+        check_finite([scale_x, scale_y])?;
+        Ok(Self::new(function, centre, scale_x, scale_y))
+    }
+
+    /// returns the scaling as a [`Transform`]
+    pub fn transform(&self) -> Transform {
+        Transform::translation(-self.centre.x, -self.centre.y)
+            .then_scale(self.scale_x, self.scale_y)
+            .then_translate(self.centre.to_vector())
+    }
+}
+
+impl ParametricFunction2D for Scale {
+    fn evaluate(&self, t: T) -> Point {
+        let val = self.function.evaluate(t);
+        let val_trans_origin: Point = (val.x - self.centre.x, val.y - self.centre.y).into();
+        let scaled: Point = (
+            val_trans_origin.x * self.scale_x,
+            val_trans_origin.y * self.scale_y,
+        )
+            .into();
+        (scaled.x + self.centre.x, scaled.y + self.centre.y).into()
+    }
+
+    fn affine_parts(&self) -> Option<(Transform, Rc<Box<dyn ParametricFunction2D>>)> {
+        Some(chain_affine(self.transform(), &self.function))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        optimize_affine(self)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Scale({}, {}, {})",
+            self.function.describe(),
+            self.scale_x,
+            self.scale_y
+        )
+    }
+}
+
+/// The shear about `centre` of a thing that implements [`ParametricFunction2D`] - `x` is displaced by `shear_x`
+/// times the height above `centre` and `y` by `shear_y` times the distance to the right of it
+#[derive(Clone)]
+pub struct Shear {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub centre: Point,
+    pub shear_x: f32,
+    pub shear_y: f32,
+}
+
+impl Shear {
+    pub fn new(function: impl IntoParametric2D, centre: Point, shear_x: f32, shear_y: f32) -> Self {
+        Self {
+            function: function.into_parametric(),
+            centre,
+            shear_x,
+            shear_y,
+        }
+    }
+
+    /// returns the shear as a [`Transform`]
+    pub fn transform(&self) -> Transform {
+        Transform::translation(-self.centre.x, -self.centre.y)
+            .then(&Transform::new(
+                1.0,
+                self.shear_y,
+                self.shear_x,
+                1.0,
+                0.0,
+                0.0,
+            ))
+            .then_translate(self.centre.to_vector())
+    }
+}
+
+impl ParametricFunction2D for Shear {
+    fn evaluate(&self, t: T) -> Point {
+        let val = self.function.evaluate(t);
+        let val_trans_origin: Point = (val.x - self.centre.x, val.y - self.centre.y).into();
+        let sheared: Point = (
+            val_trans_origin.x + self.shear_x * val_trans_origin.y,
+            val_trans_origin.y + self.shear_y * val_trans_origin.x,
+        )
+            .into();
+        (sheared.x + self.centre.x, sheared.y + self.centre.y).into()
+    }
+
+    fn affine_parts(&self) -> Option<(Transform, Rc<Box<dyn ParametricFunction2D>>)> {
+        Some(chain_affine(self.transform(), &self.function))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        optimize_affine(self)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Shear({}, {}, {})",
+            self.function.describe(),
+            self.shear_x,
+            self.shear_y
+        )
+    }
+}
+
+/// The orthogonal projection of a thing that implements [`ParametricFunction2D`] onto the (infinite) line through
+/// the ends of `line` - if `line` has zero length everything is projected onto its start
+#[derive(Clone)]
+pub struct ProjectOntoLine {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub line: Segment,
+}
+
+impl ProjectOntoLine {
+    pub fn new(function: impl IntoParametric2D, line: Segment) -> Self {
+        Self {
+            function: function.into_parametric(),
+            line,
+        }
+    }
+
+    /// returns the projection as a [`Transform`]
+    pub fn transform(&self) -> Transform {
+        let origin = self.line.start.to_vector();
+        let u = (self.line.end - self.line.start)
+            .try_normalize()
+            .unwrap_or_else(Vector::zero);
+
+        Transform::translation(-origin.x, -origin.y)
+            .then(&Transform::new(
+                u.x * u.x,
+                u.x * u.y,
+                u.x * u.y,
+                u.y * u.y,
+                0.0,
+                0.0,
+            ))
+            .then_translate(origin)
+    }
+}
+
+impl ParametricFunction2D for ProjectOntoLine {
+    fn evaluate(&self, t: T) -> Point {
+        self.transform().transform_point(self.function.evaluate(t))
+    }
+
+    fn affine_parts(&self) -> Option<(Transform, Rc<Box<dyn ParametricFunction2D>>)> {
+        Some(chain_affine(self.transform(), &self.function))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        optimize_affine(self)
+    }
+
+    fn describe(&self) -> String {
+        format!("ProjectOntoLine({})", self.function.describe())
+    }
+}
+
+/// The traversal in the opposite direction, from end to start, of a thing that implements [`ParametricFunction2D`]
+#[derive(Clone)]
+pub struct Reverse {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+}
+
+impl Reverse {
+    pub fn new(function: impl IntoParametric2D) -> Self {
+        Self {
+            function: function.into_parametric(),
+        }
+    }
+}
+
+impl ParametricFunction2D for Reverse {
+    fn evaluate(&self, t: T) -> Point {
+        self.function.evaluate(T::new(1.0 - t.value()))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(Reverse {
+            function: self.function.optimize()?,
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("Reverse({})", self.function.describe())
+    }
+}
+
+/// The part between `start` and `end` of a thing that implements [`ParametricFunction2D`] - if `end` comes before
+/// `start` the part is traversed backwards
+#[derive(Clone)]
+pub struct SubCurve {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub start: T,
+    pub end: T,
+}
+
+impl SubCurve {
+    pub fn new(function: impl IntoParametric2D, start: T, end: T) -> Self {
+        Self {
+            function: function.into_parametric(),
+            start,
+            end,
+        }
+    }
+}
+
+impl ParametricFunction2D for SubCurve {
+    fn evaluate(&self, t: T) -> Point {
+        let (start, end) = (self.start.value(), self.end.value());
+        self.function
+            .evaluate(T::new(start + (end - start) * t.value()))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(SubCurve {
+            function: self.function.optimize()?,
+            start: self.start,
+            end: self.end,
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "SubCurve({}, {}..{})",
+            self.function.describe(),
+            self.start.value(),
+            self.end.value()
+        )
+    }
+}
+
+/// A thing that implements [`ParametricFunction2D`] evaluated at `easing(t)` rather than at `t` - the result of the
+/// `easing` is clamped into a [`T`]
+#[derive(Clone)]
+pub struct EaseParam {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub easing: Rc<Box<dyn ParametricFunction1D>>,
+}
+
+impl EaseParam {
+    pub fn new(
+        function: impl IntoParametric2D,
+        easing: impl ParametricFunction1D + 'static,
+    ) -> Self {
+        Self {
+            function: function.into_parametric(),
+            easing: Rc::new(Box::new(easing)),
+        }
+    }
+}
+
+impl ParametricFunction2D for EaseParam {
+    fn evaluate(&self, t: T) -> Point {
+        self.function.evaluate(T::new(self.easing.evaluate(t)))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(EaseParam {
+            function: self.function.optimize()?,
+            easing: self.easing.clone(),
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("EaseParam({})", self.function.describe())
+    }
+}
+
+/// A thing that implements [`ParametricFunction2D`] evaluated at `t + offset` - if `wrap` is set values beyond the
+/// end come back round from the start (which suits closed curves), otherwise they are clamped
+#[derive(Clone)]
+pub struct Shift {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub offset: f32,
+    pub wrap: bool,
+}
+
+impl Shift {
+    pub fn new(function: impl IntoParametric2D, offset: f32, wrap: bool) -> Self {
+        Self {
+            function: function.into_parametric(),
+            offset,
+            wrap,
+        }
+    }
+}
+
+impl ParametricFunction2D for Shift {
+    fn evaluate(&self, t: T) -> Point {
+        let shifted = t.value() + self.offset;
+        if self.wrap && !(0.0..=1.0).contains(&shifted) {
+            return self.function.evaluate(T::new(shifted.rem_euclid(1.0)));
+        }
+        self.function.evaluate(T::new(shifted))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(Shift {
+            function: self.function.optimize()?,
+            offset: self.offset,
+            wrap: self.wrap,
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("Shift({}, {})", self.function.describe(), self.offset)
+    }
+}
+
+/// A thing that implements [`ParametricFunction2D`] with `f` applied to each of its points - an escape hatch for
+/// distortions (fisheyes, wrapping round a circle and so on) that don't have a combinator of their own
+#[derive(Clone)]
+pub struct Map {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub f: Rc<Box<dyn Fn(Point) -> Point>>,
+}
+
+impl Map {
+    pub fn new(function: impl IntoParametric2D, f: impl Fn(Point) -> Point + 'static) -> Self {
+        Self {
+            function: function.into_parametric(),
+            f: Rc::new(Box::new(f)),
+        }
+    }
+}
+
+impl ParametricFunction2D for Map {
+    fn evaluate(&self, t: T) -> Point {
+        (self.f)(self.function.evaluate(t))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(Map {
+            function: self.function.optimize()?,
+            f: self.f.clone(),
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("Map({})", self.function.describe())
+    }
+}
+
+/// The blend of two things that implement [`ParametricFunction2D`], evaluating both at the same `t` and interpolating
+/// linearly between them - an `amount` of `0.0` gives `a` and `1.0` gives `b`
+#[derive(Clone)]
+pub struct Morph {
+    pub a: Rc<Box<dyn ParametricFunction2D>>,
+    pub b: Rc<Box<dyn ParametricFunction2D>>,
+    pub amount: f32,
+}
+
+impl Morph {
+    pub fn new(a: impl IntoParametric2D, b: impl IntoParametric2D, amount: f32) -> Self {
+        Self {
+            a: a.into_parametric(),
+            b: b.into_parametric(),
+            amount,
+        }
+    }
+}
+
+impl ParametricFunction2D for Morph {
+    fn evaluate(&self, t: T) -> Point {
+        self.a.evaluate(t).lerp(self.b.evaluate(t), self.amount)
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        let [a, b] = optimize_all(&[self.a.clone(), self.b.clone()])?
+            .try_into()
+            .ok()?;
+        Some(Rc::new(Box::new(Morph {
+            a,
+            b,
+            amount: self.amount,
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Morph({}, {}, {})",
+            self.a.describe(),
+            self.b.describe(),
+            self.amount
+        )
+    }
+}
+
+/// Like [`Morph`], but with the amount varying along the curve - at each `t` it is `amount(t)`
+#[derive(Clone)]
+pub struct MorphBy {
+    pub a: Rc<Box<dyn ParametricFunction2D>>,
+    pub b: Rc<Box<dyn ParametricFunction2D>>,
+    pub amount: Rc<Box<dyn ParametricFunction1D>>,
+}
+
+impl MorphBy {
+    pub fn new(
+        a: impl IntoParametric2D,
+        b: impl IntoParametric2D,
+        amount: impl ParametricFunction1D + 'static,
+    ) -> Self {
+        Self {
+            a: a.into_parametric(),
+            b: b.into_parametric(),
+            amount: Rc::new(Box::new(amount)),
+        }
+    }
+}
+
+impl ParametricFunction2D for MorphBy {
+    fn evaluate(&self, t: T) -> Point {
+        self.a
+            .evaluate(t)
+            .lerp(self.b.evaluate(t), self.amount.evaluate(t))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        let [a, b] = optimize_all(&[self.a.clone(), self.b.clone()])?
+            .try_into()
+            .ok()?;
+        Some(Rc::new(Box::new(MorphBy {
+            a,
+            b,
+            amount: self.amount.clone(),
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("MorphBy({}, {})", self.a.describe(), self.b.describe())
+    }
+}
+
+/// The weighted average of several things that implement [`ParametricFunction2D`], all evaluated at the same `t` -
+/// the weight of each at `t` is the value of the corresponding entry of `weights`. The weights are normalised so they
+/// needn't add up to one, and if they add up to zero the functions are weighted equally. Functions without a
+/// weight (or weights without a function) are ignored.
+#[derive(Clone)]
+pub struct Blend {
+    pub functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
+    pub weights: Vec<Rc<Box<dyn ParametricFunction1D>>>,
+}
+
+impl Blend {
+    pub fn new(
+        functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
+        weights: Vec<Box<dyn ParametricFunction1D>>,
+    ) -> Self {
+        Self {
+            functions,
+            weights: weights.into_iter().map(Rc::new).collect(),
+        }
+    }
+}
+
+impl ParametricFunction2D for Blend {
+    fn evaluate(&self, t: T) -> Point {
+        let pairs: Vec<_> = self
+            .functions
+            .iter()
+            .zip(&self.weights)
+            .map(|(function, weight)| (function.evaluate(t).to_vector(), weight.evaluate(t)))
+            .collect();
+        let total: f32 = pairs.iter().map(|&(_, weight)| weight).sum();
+
+        let sum = if total == 0.0 {
+            pairs.iter().map(|&(p, _)| p).sum::<Vector>() / pairs.len().max(1) as f32
+        } else {
+            pairs.iter().map(|&(p, weight)| p * weight).sum::<Vector>() / total
+        };
+        sum.to_point()
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(Blend {
+            functions: optimize_all(&self.functions)?,
+            weights: self.weights.clone(),
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("Blend[{}]", describe_all(&self.functions))
+    }
+}
+
+/// The transformation by `transform` of a thing that implements [`ParametricFunction2D`] - any of the other affine
+/// combinators can be expressed as one of these, see [`compile`]
+#[derive(Clone)]
+pub struct Affine {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub transform: Transform,
+}
+
+impl Affine {
+    pub fn new(function: impl IntoParametric2D, transform: Transform) -> Self {
+        Self {
+            function: function.into_parametric(),
+            transform,
+        }
+    }
+}
+
+impl ParametricFunction2D for Affine {
+    fn evaluate(&self, t: T) -> Point {
+        self.transform.transform_point(self.function.evaluate(t))
+    }
+
+    fn affine_parts(&self) -> Option<(Transform, Rc<Box<dyn ParametricFunction2D>>)> {
+        Some(chain_affine(self.transform, &self.function))
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        optimize_affine(self)
+    }
+
+    fn describe(&self) -> String {
+        format!("Affine({})", self.function.describe())
+    }
+}
+
+/// returns an equivalent of `function` where each chain of nested [`Translate`], [`Rotate`], [`RotateTranslate`],
+/// [`Scale`], [`Shear`] and [`Affine`] combinators is collapsed into a single [`Affine`], so that evaluating it
+/// costs one matrix multiplication per chain rather than one trip through every combinator. The crate's combinators
+/// are all looked through, but closures and other implementations that don't override
+/// [`ParametricFunction2D::optimize`] are left as they are, along with anything beneath them.
+pub fn compile(function: impl IntoParametric2D) -> Rc<Box<dyn ParametricFunction2D>> {
+    let function = function.into_parametric();
+    function.optimize().unwrap_or(function)
+}
+
+/// folds `own`, the transform of an affine combinator, together with those of any affine combinators directly
+/// beneath it
+fn chain_affine(
+    own: Transform,
+    function: &Rc<Box<dyn ParametricFunction2D>>,
+) -> (Transform, Rc<Box<dyn ParametricFunction2D>>) {
+    match function.affine_parts() {
+        Some((inner, leaf)) => (inner.then(&own), leaf),
+        None => (own, function.clone()),
+    }
+}
+
+fn optimize_affine(
+    function: &dyn ParametricFunction2D,
+) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+    let (transform, leaf) = function.affine_parts()?;
+    let leaf = leaf.optimize().unwrap_or(leaf);
+    Some(Rc::new(Box::new(Affine {
+        function: leaf,
+        transform,
+    })))
 }
 
-impl ParametricFunction2D for Scale {
-    fn evaluate(&self, t: T) -> Point {
-        let val = self.function.evaluate(t);
-        let val_trans_origin: Point = (val.x - self.centre.x, val.y - self.centre.y).into();
-        let scaled: Point = (
-            val_trans_origin.x * self.scale_x,
-            val_trans_origin.y * self.scale_y,
-        )
-            .into();
-        (scaled.x + self.centre.x, scaled.y + self.centre.y).into()
+/// optimizes each of `functions`, `None` if none of them change
+pub(crate) fn optimize_all(
+    functions: &[Rc<Box<dyn ParametricFunction2D>>],
+) -> Option<Vec<Rc<Box<dyn ParametricFunction2D>>>> {
+    let optimized: Vec<_> = functions.iter().map(|f| f.optimize()).collect();
+    if optimized.iter().all(Option::is_none) {
+        return None;
     }
+    Some(
+        optimized
+            .into_iter()
+            .zip(functions)
+            .map(|(o, f)| o.unwrap_or_else(|| f.clone()))
+            .collect(),
+    )
 }
+
 #[cfg(test)]
 mod tests {
     use approx::assert_relative_eq;
 
-    use crate::{segment::Segment, Circle};
+    use crate::Circle;
 
     use super::*;
 
@@ -313,6 +2053,61 @@ mod tests {
         assert_relative_eq!(res.y, 0.0);
     }
 
+    #[test]
+    fn test_ping_pong() {
+        let s = Segment::new((0.0, 0.0).into(), (1.0, 1.0).into());
+        let pp = PingPong::new(s, 3);
+
+        let res = pp.evaluate(T::new(1.0 / 3.0));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+
+        let res = pp.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.5, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.5, epsilon = 1e-5);
+
+        let res = pp.evaluate(T::new(2.0 / 3.0 + 1e-6));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-5);
+
+        let res = pp.end();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.0);
+    }
+
+    #[test]
+    fn test_t_arithmetic() {
+        let (a, b) = (T::new(0.25), T::new(0.75));
+        assert_relative_eq!((a + b).value(), 1.0);
+        assert_relative_eq!((b + b).value(), 1.0);
+        assert_relative_eq!((b - a).value(), 0.5);
+        assert_relative_eq!((a - b).value(), 0.0);
+        assert_relative_eq!((a * 2.0).value(), 0.5);
+        assert_relative_eq!((b * 2.0).value(), 1.0);
+
+        assert_relative_eq!(T::lerp(a, b, 0.5).value(), 0.5);
+        assert_relative_eq!(T::lerp(a, b, 4.0).value(), 1.0);
+        assert_relative_eq!(T::from_fraction(3, 4).value(), 0.75);
+        assert_relative_eq!(T::from_fraction(3, 0).value(), 0.0);
+        assert_relative_eq!(a.inverse().value(), 0.75);
+        assert_eq!(T::end().inverse(), T::start());
+    }
+
+    #[test]
+    fn test_t_range() {
+        let ts: Vec<f32> = T::range(4).map(|t| t.value()).collect();
+        assert_eq!(ts, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(T::range(3).len(), 4);
+        assert_eq!(T::range(3).last(), Some(T::end()));
+        assert_eq!(T::range(0).collect::<Vec<_>>(), vec![T::start()]);
+
+        let ts: Vec<f32> = T::stepped(0.1).map(|t| t.value()).collect();
+        assert_eq!(ts.len(), 11);
+        assert_relative_eq!(ts[3], 0.3);
+        assert_eq!(T::stepped(0.4).len(), 3);
+        assert_eq!(T::stepped(-1.0).len(), 1);
+    }
+
     #[test]
     fn test_concat() {
         let s1 = Segment {
@@ -344,6 +2139,406 @@ mod tests {
         assert_relative_eq!(res.y, 1.0);
     }
 
+    #[test]
+    fn test_weighted_concat() {
+        let s1 = Segment::new((0.0, 0.0).into(), (1.0, 0.0).into());
+        let s2 = Segment::new((1.0, 0.0).into(), (1.0, 3.0).into());
+
+        let concat = WeightedConcat::new(
+            vec![Rc::new(Box::new(s1)), Rc::new(Box::new(s2))],
+            vec![1.0, 3.0],
+        );
+
+        let res = concat.start();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = concat.evaluate(T::new(0.125));
+        assert_relative_eq!(res.x, 0.5);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = concat.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = concat.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = concat.end();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 3.0);
+    }
+
+    #[test]
+    fn test_weighted_concat_empty() {
+        let empty = WeightedConcat::new(vec![], vec![]);
+        for t in [T::start(), T::new(0.5), T::end()] {
+            assert_eq!(empty.evaluate(t), Point::origin());
+        }
+    }
+
+    #[test]
+    fn test_arc_length() {
+        let s = Segment::new((0.0, 0.0).into(), (3.0, 4.0).into());
+        assert_relative_eq!(s.arc_length(1e-4), 5.0, epsilon = 1e-4);
+        assert_relative_eq!(
+            s.arc_length_between(T::new(0.2), T::new(0.6), 1e-4),
+            2.0,
+            epsilon = 1e-4
+        );
+
+        let c = Circle::new((0.0, 0.0).into(), 2.0, None);
+        assert_relative_eq!(
+            c.arc_length(1e-4),
+            2.0 * std::f32::consts::TAU,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_approximate_with_cubics() {
+        let wave = |t: T| Point::new(t.value() * 4.0, (t.value() * 12.0).sin());
+        let spline = wave.approximate_with_cubics(1e-3);
+
+        let pieces = (spline.points.len() - 1) / 3;
+        assert!(pieces > 1);
+        assert_eq!(spline.points.len(), pieces * 3 + 1);
+        for (i, p) in spline.points.iter().step_by(3).enumerate() {
+            if i > 0 && i < pieces {
+                // every piece starts and ends on the curve
+                assert_relative_eq!(p.y, (p.x * 3.0).sin(), epsilon = 1e-5);
+            }
+        }
+        for p in spline.linspace(200) {
+            assert_relative_eq!(p.y, (p.x * 3.0).sin(), epsilon = 5e-3);
+        }
+
+        // a straight line only needs the one piece
+        let s = Segment::new((0.0, 0.0).into(), (1.0, 1.0).into());
+        assert_eq!(s.approximate_with_cubics(1e-3).points.len(), 4);
+    }
+
+    #[test]
+    fn test_concat_welded() {
+        let functions = || -> Vec<Rc<Box<dyn ParametricFunction2D>>> {
+            vec![
+                Segment::new((0.0, 0.0).into(), (1.0, 0.0).into()).into_parametric(),
+                Segment::new((1.0, 0.002).into(), (1.0, 1.0).into()).into_parametric(),
+                Segment::new((2.0, 1.0).into(), (3.0, 1.0).into()).into_parametric(),
+            ]
+        };
+
+        // the hairline is closed up halfway, leaving the far ends where they were
+        let concat = Concat::welded(functions(), 0.01, false).unwrap();
+        assert_eq!(concat.functions.len(), 3);
+        let (a, b) = (concat.functions[0].end(), concat.functions[1].start());
+        assert_relative_eq!(a.x, b.x);
+        assert_relative_eq!(a.y, 0.001);
+        assert_relative_eq!(b.y, 0.001);
+        let res = concat.functions[1].end();
+        assert_relative_eq!(res.y, 1.0);
+        assert_eq!(concat.analyze_continuity(0.01).len(), 2);
+
+        // and the wider gap bridged
+        let concat = Concat::welded(functions(), 0.01, true).unwrap();
+        assert_eq!(concat.functions.len(), 4);
+        let res = concat.evaluate(T::new(0.625));
+        assert_relative_eq!(res.x, 1.5);
+        assert_relative_eq!(res.y, 1.0);
+        assert!(Concat::new_continuous(concat.functions, 1e-6).is_ok());
+
+        assert!(Concat::welded(vec![], 0.01, true).is_err());
+    }
+
+    #[test]
+    fn test_frame_at() {
+        let s = Segment::new((1.0, 1.0).into(), (1.0, 3.0).into());
+        let frame = s.frame_at(T::new(0.5));
+        assert_relative_eq!(frame.position.y, 2.0);
+        assert_relative_eq!(frame.tangent.y, 1.0, epsilon = 1e-4);
+        assert_relative_eq!(frame.normal.x, -1.0, epsilon = 1e-4);
+        assert_relative_eq!(frame.angle(), 0.25, epsilon = 1e-4);
+
+        // an arrowhead barb, behind and to the left
+        let res = frame.to_world((-0.5, 0.25).into());
+        assert_relative_eq!(res.x, 0.75, epsilon = 1e-4);
+        assert_relative_eq!(res.y, 1.5, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_stamps_along() {
+        let c = Concat::new(vec![
+            Segment::new((0.0, 0.0).into(), (1.0, 0.0).into()).into_parametric(),
+            Segment::new((1.0, 0.0).into(), (1.0, 3.0).into()).into_parametric(),
+        ])
+        .unwrap();
+
+        let stamps = c.stamps_along(Spacing::Count(5), true);
+        assert_eq!(stamps.len(), 5);
+        let (p, _) = stamps[1];
+        assert_relative_eq!(p.x, 1.0, epsilon = 1e-3);
+        assert_relative_eq!(p.y, 0.0, epsilon = 1e-3);
+        let (p, angle) = stamps[2];
+        assert_relative_eq!(p.y, 1.0, epsilon = 1e-3);
+        assert_relative_eq!(angle, 0.25, epsilon = 1e-3);
+
+        // by t instead, the first piece gets half of them
+        let stamps = c.stamps_along(Spacing::Count(5), false);
+        let (p, angle) = stamps[1];
+        assert_relative_eq!(p.x, 0.5, epsilon = 1e-4);
+        assert_relative_eq!(angle, 0.0, epsilon = 1e-3);
+
+        let stamps = c.stamps_along(Spacing::Every(1.5), true);
+        assert_eq!(stamps.len(), 3);
+        let (p, _) = stamps[2];
+        assert_relative_eq!(p.y, 2.0, epsilon = 1e-3);
+
+        let stamps = c.stamps_along(Spacing::Every(0.25), false);
+        assert_eq!(stamps.len(), 5);
+        assert!(c.stamps_along(Spacing::Count(0), true).is_empty());
+    }
+
+    #[test]
+    fn test_random_seeded() {
+        let s = Segment::new((0.0, 0.0).into(), (1.0, 0.0).into());
+
+        crate::random::seed(42);
+        let a = s.random_points(10);
+        crate::random::seed(42);
+        let b = s.random_points(10);
+        crate::random::unseed();
+        assert_eq!(a, b);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let a = s.random_point_with_rng(&mut rng);
+        let mut rng = StdRng::seed_from_u64(7);
+        let b = s.random_points_with_rng(3, &mut rng);
+        assert_eq!(a, b[0]);
+
+        let f = |t: T| t.value();
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_relative_eq!(f.random_point_with_rng(&mut rng), a.x);
+    }
+
+    #[test]
+    fn test_random_points_with() {
+        let s = Segment::new((0.0, 0.0).into(), (4.0, 0.0).into());
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let points =
+            s.random_points_with(100, rand::distributions::Uniform::new(0.0, 0.25), &mut rng);
+        assert_eq!(points.len(), 100);
+        assert!(points.iter().all(|p| p.x < 1.0));
+
+        // the larger of two uniform samples is bunched towards the end
+        let points = s.random_points_with(
+            1000,
+            rand::distributions::Standard.map(|(a, b): (f32, f32)| a.max(b)),
+            &mut rng,
+        );
+        let past_middle = points.iter().filter(|p| p.x > 2.0).count();
+        assert!(past_middle > 700);
+    }
+
+    #[test]
+    fn test_random_points_uniform_arclength() {
+        let c = Concat::new(vec![
+            Segment::new((0.0, 0.0).into(), (1.0, 0.0).into()).into_parametric(),
+            Segment::new((1.0, 0.0).into(), (1.0, 9.0).into()).into_parametric(),
+        ])
+        .unwrap();
+
+        // the first piece is a tenth of the length, though half of t
+        let mut rng = StdRng::seed_from_u64(3);
+        let points = c.random_points_uniform_arclength(2000, &mut rng);
+        let on_first = points.iter().filter(|p| p.y == 0.0 && p.x < 1.0).count();
+        assert!((150..250).contains(&on_first));
+    }
+
+    #[test]
+    fn test_evaluate_unclamped() {
+        let s = Segment::new((0.0, 0.0).into(), (2.0, 1.0).into());
+        let res = s.evaluate_unclamped(1.5);
+        assert_relative_eq!(res.x, 3.0);
+        assert_relative_eq!(res.y, 1.5);
+        let res = s.evaluate_unclamped(-1.0);
+        assert_relative_eq!(res.x, -2.0);
+        assert_relative_eq!(res.y, -1.0);
+        let res = s.evaluate_unclamped(0.5);
+        assert_relative_eq!(res.x, 1.0);
+
+        // a Bezier carries on along its tangent
+        let b = crate::bezier::BezierSecond::new(
+            (0.0, 0.0).into(),
+            (2.0, 0.0).into(),
+            (1.0, 1.0).into(),
+        );
+        let res = b.evaluate_unclamped(2.0);
+        assert_relative_eq!(res.x, 4.0, epsilon = 1e-3);
+        assert_relative_eq!(res.y, -2.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_map() {
+        // wrapping a line round the unit circle
+        let s = Segment::new((0.0, 1.0).into(), (0.5, 1.0).into());
+        let m = Map::new(s, |p: Point| {
+            let angle = p.x * std::f32::consts::TAU;
+            (p.y * angle.cos(), p.y * angle.sin()).into()
+        });
+
+        let res = m.start();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = m.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = m.end();
+        assert_relative_eq!(res.x, -1.0);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_concat_by_arc_length() {
+        let s1 = Segment::new((0.0, 0.0).into(), (1.0, 0.0).into());
+        let s2 = Segment::new((1.0, 0.0).into(), (1.0, 3.0).into());
+
+        let concat =
+            Concat::by_arc_length(vec![Rc::new(Box::new(s1)), Rc::new(Box::new(s2))], 1e-4);
+
+        let res = concat.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-4);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-4);
+
+        let res = concat.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-4);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_from_curves() {
+        let s1 = Segment::new((0.0, 0.0).into(), (1.0, 0.0).into());
+        let s2 = Segment::new((1.0, 0.0).into(), (1.0, 1.0).into());
+        let concat = Concat::from_curves([boxed(s1), boxed(s2)]).unwrap();
+        assert_eq!(concat.functions.len(), 2);
+
+        let res = concat.evaluate(T::new(0.75));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.5);
+
+        assert_eq!(
+            Concat::from_curves(std::iter::empty()).err(),
+            Some(ParametricsError::Empty)
+        );
+    }
+
+    #[test]
+    fn test_concat_collect() {
+        let mut concat: Concat = (0..2)
+            .map(|i| Segment::new((i as f32, 0.0).into(), (i as f32 + 1.0, 0.0).into()))
+            .collect();
+        concat.push(Segment::new((2.0, 0.0).into(), (2.0, 1.0).into()));
+        concat.extend([boxed(Segment::new((2.0, 1.0).into(), (3.0, 1.0).into()))]);
+        assert_eq!(concat.functions.len(), 4);
+
+        let res = concat.evaluate(T::new(0.625));
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 0.5);
+
+        let empty: Concat = std::iter::empty::<DynCurve>().collect();
+        assert_eq!(empty.evaluate(T::new(0.5)), Point::origin());
+    }
+
+    #[test]
+    fn test_concat_new() {
+        assert_eq!(Concat::new(vec![]).err(), Some(ParametricsError::Empty));
+
+        let s1 = Segment::new((0.0, 0.0).into(), (1.0, 0.0).into());
+        let s2 = Segment::new((1.0, 0.0).into(), (1.0, 1.0).into());
+        let s3 = Segment::new((1.0, 1.5).into(), (0.0, 1.5).into());
+        let functions: Vec<Rc<Box<dyn ParametricFunction2D>>> = vec![
+            Rc::new(Box::new(s1)),
+            Rc::new(Box::new(s2)),
+            Rc::new(Box::new(s3)),
+        ];
+
+        assert!(Concat::new(functions.clone()).is_ok());
+        assert!(Concat::new_continuous(functions[..2].to_vec(), 1e-6).is_ok());
+        assert!(Concat::new_continuous(functions.clone(), 0.6).is_ok());
+
+        match Concat::new_continuous(functions, 0.1) {
+            Err(ParametricsError::Discontinuous { index, gap }) => {
+                assert_eq!(index, 1);
+                assert_relative_eq!(gap, 0.5);
+            }
+            _ => panic!("expected a discontinuity"),
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        let s1 = Segment::new((0.0, 0.0).into(), (1.0, 0.0).into());
+        let b = BezierThird::new(
+            (1.0, 0.0).into(),
+            (2.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (2.0, 1.0).into(),
+        );
+        let concat = Concat::new(vec![s1.into_parametric(), b.into_parametric()]).unwrap();
+        let rotated = Rotate::new(concat, (0.0, 0.0).into(), Angle::turns(0.25));
+        assert_eq!(
+            rotated.describe(),
+            "Rotate(Concat[Segment, BezierThird], 0.25turn)"
+        );
+
+        let closure = |t: T| -> Point { (t.value(), 0.0).into() };
+        assert_eq!(Reverse::new(closure).describe(), "Reverse(Fn)");
+        assert_eq!(
+            crate::bezier::BezierNth::new([Point::origin(); 4]).describe(),
+            "BezierNth<4>"
+        );
+    }
+
+    #[test]
+    fn test_try_new_combinators() {
+        let s = || Segment::new((0.0, 0.0).into(), (1.0, 0.0).into());
+        let functions = || vec![s().into_parametric(), s().into_parametric()];
+
+        assert!(WeightedConcat::try_new(functions(), vec![1.0, 2.0]).is_ok());
+        assert_eq!(
+            WeightedConcat::try_new(vec![], vec![]).err(),
+            Some(ParametricsError::Empty)
+        );
+        assert_eq!(
+            WeightedConcat::try_new(functions(), vec![1.0]).err(),
+            Some(ParametricsError::LengthMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            WeightedConcat::try_new(functions(), vec![0.0, 0.0]).err(),
+            Some(ParametricsError::InvalidWeights)
+        );
+        assert_eq!(
+            WeightedConcat::try_new(functions(), vec![f32::NAN, 1.0]).err(),
+            Some(ParametricsError::NotFinite)
+        );
+
+        assert!(Repeat::try_new(s(), 2).is_ok());
+        assert_eq!(
+            Repeat::try_new(s(), 0).err(),
+            Some(ParametricsError::ZeroCount)
+        );
+        assert_eq!(
+            PingPong::try_new(s(), 0).err(),
+            Some(ParametricsError::ZeroCount)
+        );
+    }
+
     #[test]
     fn test_concat_repeat() {
         let s1 = Segment {
@@ -396,6 +2591,39 @@ mod tests {
         assert_eq!(ps.len(), 100)
     }
 
+    #[test]
+    fn test_tangent() {
+        let s = Segment::new((0.0, 0.0).into(), (3.0, 4.0).into());
+
+        let d = s.derivative(T::new(0.5));
+        assert_relative_eq!(d.x, 3.0, epsilon = 1e-3);
+        assert_relative_eq!(d.y, 4.0, epsilon = 1e-3);
+
+        let tangent = s.tangent(T::end());
+        assert_relative_eq!(tangent.x, 0.6, epsilon = 1e-3);
+        assert_relative_eq!(tangent.y, 0.8, epsilon = 1e-3);
+
+        let c = Circle::new((0.0, 0.0).into(), 1.0, None);
+        let tangent = c.tangent(T::new(0.25));
+        assert_relative_eq!(tangent.x, -1.0, epsilon = 1e-3);
+        assert_relative_eq!(tangent.y, 0.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_closest_point() {
+        let c = Circle::new((0.0, 0.0).into(), 1.0, None);
+
+        let t = c.closest_t((0.0, 3.0).into(), 16);
+        assert_relative_eq!(t.value(), 0.25, epsilon = 1e-4);
+
+        let p = c.closest_point((3.0, 0.0).into(), 16);
+        assert_relative_eq!(p.x, 1.0, epsilon = 1e-4);
+        assert_relative_eq!(p.y, 0.0, epsilon = 1e-4);
+
+        let d = c.distance_to((0.0, -2.0).into(), 7);
+        assert_relative_eq!(d, 1.0, epsilon = 1e-4);
+    }
+
     #[test]
     fn test_rotate() {
         let s = Segment {
@@ -405,7 +2633,7 @@ mod tests {
         let r = Rotate {
             function: Rc::new(Box::new(s)),
             centre: (0.5, 0.5).into(),
-            angle: T::new(0.25),
+            angle: Angle::turns(0.25),
         };
 
         let t = T::start();
@@ -454,7 +2682,7 @@ mod tests {
         let r_tr = RotateTranslate {
             function: Rc::new(Box::new(s)),
             centre: (0.5, 0.5).into(),
-            angle: T::new(0.25),
+            angle: Angle::turns(0.25),
             by: (0.5, 0.5).into(),
             rotate_first: true,
         };
@@ -478,7 +2706,7 @@ mod tests {
         let r_tr = RotateTranslate {
             function: Rc::new(Box::new(s)),
             centre: (0.5, 0.5).into(),
-            angle: T::new(0.25),
+            angle: Angle::turns(0.25),
             by: (0.5, 0.5).into(),
             rotate_first: false,
         };
@@ -532,6 +2760,263 @@ mod tests {
         assert_relative_eq!(res.y, 0.0);
     }
 
+    #[test]
+    fn test_zip() {
+        let ramp = |t: T| t.value() * 2.0;
+        let z = Zip::new(ramp, |t: T| 1.0 - t.value());
+        let res = z.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 0.5);
+        assert_relative_eq!(res.y, 0.75);
+
+        // y runs at half speed, starting halfway along
+        let z = Zip::warped(ramp, ramp, |t: T| t.value(), |t: T| 0.5 + t.value() / 2.0);
+        let res = z.start();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 1.0);
+        let res = z.end();
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 2.0);
+
+        // warps are clamped
+        let z = Zip::warped(ramp, ramp, |t: T| t.value() * 3.0, |t: T| -t.value());
+        let res = z.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 0.0);
+        assert_eq!(Reverse::new(z).describe(), "Reverse(Zip)");
+    }
+
+    #[test]
+    fn test_into_parametric() {
+        let points: Vec<Point> = vec![(0.0, 0.0).into(), (1.0, 0.0).into(), (1.0, 1.0).into()];
+        let polyline = points.into_parametric();
+
+        let res = polyline.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = polyline.evaluate(T::new(0.75));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.5);
+
+        let r = Rotate::new(polyline, (0.0, 0.0).into(), T::new(0.5));
+        let res = r.end();
+        assert_relative_eq!(res.x, -1.0, epsilon = f32::EPSILON * 10.0);
+        assert_relative_eq!(res.y, -1.0, epsilon = f32::EPSILON * 10.0);
+
+        let tr = Translate::new(|t: T| Point::new(t.value(), 0.0), (0.0, 1.0).into());
+        let res = tr.end();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let single = vec![Point::new(2.0, 3.0)].into_parametric();
+        let res = single.evaluate(T::new(0.3));
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 3.0);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let s = Segment::new((0.0, 0.0).into(), (1.0, 2.0).into());
+        let r = Reverse::new(s);
+
+        let res = r.start();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 2.0);
+
+        let res = r.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 0.75);
+        assert_relative_eq!(res.y, 1.5);
+    }
+
+    #[test]
+    fn test_sub_curve() {
+        let s = Segment::new((0.0, 0.0).into(), (1.0, 2.0).into());
+        let sub = SubCurve::new(s, T::new(0.25), T::new(0.75));
+
+        let res = sub.start();
+        assert_relative_eq!(res.x, 0.25);
+        assert_relative_eq!(res.y, 0.5);
+
+        let res = sub.end();
+        assert_relative_eq!(res.x, 0.75);
+        assert_relative_eq!(res.y, 1.5);
+
+        let s = Segment::new((0.0, 0.0).into(), (1.0, 2.0).into());
+        let sub = SubCurve::new(s, T::new(0.5), T::start());
+
+        let res = sub.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.25);
+        assert_relative_eq!(res.y, 0.5);
+    }
+
+    #[test]
+    fn test_ease_param() {
+        let s = Segment::new((0.0, 0.0).into(), (1.0, 2.0).into());
+        let eased = EaseParam::new(s, |t: T| t.value() * t.value());
+
+        let res = eased.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.25);
+        assert_relative_eq!(res.y, 0.5);
+
+        let res = eased.end();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 2.0);
+    }
+
+    #[test]
+    fn test_morph() {
+        let s = Segment::new((0.0, 0.0).into(), (2.0, 0.0).into());
+        let c = Circle::new((1.0, 0.0).into(), 1.0, Some(T::new(0.5)));
+
+        let m = Morph::new(s, c, 0.5);
+        let res = m.evaluate(T::new(0.25));
+        assert_relative_eq!(
+            res.x,
+            0.5 * (0.5 + 1.0 + f32::cos(0.75 * std::f32::consts::TAU)),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            res.y,
+            0.5 * f32::sin(0.75 * std::f32::consts::TAU),
+            epsilon = 1e-6
+        );
+
+        let res = m.start();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+
+        let m = MorphBy::new(m.a.clone(), m.b.clone(), |t: T| t.value());
+        let res = m.start();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+
+        // halfway between (1, 0) on the segment and (2, 0) on the circle
+        let res = m.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.5, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+
+        // compiling collapses the transforms inside, and keeps the amount
+        let shifted = Translate::new(
+            Translate::new(m.a.clone(), (1.0, 0.0).into()),
+            (0.0, 1.0).into(),
+        );
+        let m = MorphBy::new(shifted, m.b.clone(), |t: T| t.value());
+        let expected = m.linspace(10);
+        let compiled = compile(m);
+        assert_eq!(compiled.describe(), "MorphBy(Affine(Segment), Circle)");
+        for (a, b) in compiled.linspace(10).into_iter().zip(expected) {
+            assert_relative_eq!(a.x, b.x, epsilon = 1e-5);
+            assert_relative_eq!(a.y, b.y, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_project_onto_line() {
+        let c = Circle::new((0.0, 0.0).into(), 1.0, None);
+        let line = Segment::new((0.0, 1.0).into(), (1.0, 2.0).into());
+        let p = ProjectOntoLine::new(c, line);
+
+        // everything ends up on the line y = x + 1
+        for res in p.linspace(10) {
+            assert_relative_eq!(res.y, res.x + 1.0, epsilon = 1e-5);
+        }
+
+        // (1, 0) projects onto the foot of the perpendicular from it
+        let res = p.start();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        let res = p.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        let res = p.evaluate(T::new(0.125));
+        assert_relative_eq!(res.x, f32::sqrt(0.5) - 0.5, epsilon = 1e-6);
+        assert_relative_eq!(res.y, f32::sqrt(0.5) + 0.5, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_blend() {
+        let functions = vec![
+            Segment::new((0.0, 0.0).into(), (1.0, 0.0).into()).into_parametric(),
+            Segment::new((0.0, 1.0).into(), (1.0, 1.0).into()).into_parametric(),
+            Segment::new((0.0, 2.0).into(), (1.0, 2.0).into()).into_parametric(),
+        ];
+        let weights: Vec<Box<dyn ParametricFunction1D>> = vec![
+            Box::new(|t: T| 1.0 - t.value()),
+            Box::new(|_: T| 0.0),
+            Box::new(|t: T| 3.0 * t.value()),
+        ];
+        let b = Blend::new(functions, weights);
+
+        let res = b.start();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = b.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.5);
+        assert_relative_eq!(res.y, 1.5);
+
+        let res = b.end();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 2.0);
+
+        // equally weighted when the weights cancel out
+        let b = Blend::new(
+            b.functions.clone(),
+            vec![
+                Box::new(|_: T| 1.0),
+                Box::new(|_: T| -1.0),
+                Box::new(|_: T| 0.0),
+            ],
+        );
+        let res = b.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.5);
+        assert_relative_eq!(res.y, 1.0);
+
+        // compiling collapses the transforms inside, and keeps the weights
+        let b = Blend {
+            functions: vec![
+                b.functions[0].clone(),
+                Translate::new(
+                    Scale::new(b.functions[1].clone(), Point::origin(), 2.0, 2.0),
+                    (1.0, 0.0).into(),
+                )
+                .into_parametric(),
+            ],
+            weights: b.weights.clone(),
+        };
+        let expected = b.linspace(10);
+        let compiled = compile(b);
+        assert_eq!(compiled.describe(), "Blend[Segment, Affine(Segment)]");
+        for (a, b) in compiled.linspace(10).into_iter().zip(expected) {
+            assert_relative_eq!(a.x, b.x, epsilon = 1e-5);
+            assert_relative_eq!(a.y, b.y, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_shift() {
+        let c = Circle::new((0.0, 0.0).into(), 1.0, None);
+        let shifted = Shift::new(c, 0.25, true);
+
+        let res = shifted.start();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        let res = shifted.evaluate(T::new(0.875));
+        assert_relative_eq!(res.x, f32::sqrt(0.5), epsilon = 1e-6);
+        assert_relative_eq!(res.y, f32::sqrt(0.5), epsilon = 1e-6);
+
+        let s = Segment::new((0.0, 0.0).into(), (1.0, 0.0).into());
+        let shifted = Shift::new(s, 0.5, false);
+
+        let res = shifted.start();
+        assert_relative_eq!(res.x, 0.5);
+
+        let res = shifted.evaluate(T::new(0.75));
+        assert_relative_eq!(res.x, 1.0);
+    }
+
     #[test]
     fn test_scale() {
         let c = Circle::new((1.0, 1.0).into(), 10.0, None);
@@ -550,4 +3035,120 @@ mod tests {
         assert_relative_eq!(s.x, 1.0, epsilon = f32::EPSILON * 10.0);
         assert_relative_eq!(s.y, 21.0, epsilon = f32::EPSILON * 10.0);
     }
+
+    #[test]
+    fn test_normal() {
+        let s = Segment::new((0.0, 0.0).into(), (2.0, 0.0).into());
+        let res = s.normal(T::new(0.5));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_compile() {
+        let s: Rc<Box<dyn ParametricFunction2D>> =
+            Rc::new(Box::new(Segment::new((0.0, 0.0).into(), (1.0, 2.0).into())));
+
+        let nested = Translate::new(
+            Rotate::new(
+                Scale::new(
+                    Shear::new(s.clone(), (0.5, 0.5).into(), 0.3, -0.2),
+                    (1.0, 0.0).into(),
+                    2.0,
+                    0.5,
+                ),
+                (0.0, 1.0).into(),
+                T::new(0.1),
+            ),
+            (3.0, -1.0).into(),
+        );
+        let nested = RotateTranslate::new(
+            nested,
+            (1.0, 1.0).into(),
+            (2.0, 2.0).into(),
+            T::new(0.3),
+            false,
+        );
+
+        let (_, leaf) = nested.affine_parts().unwrap();
+        assert!(Rc::ptr_eq(&leaf, &s));
+
+        let concat = Concat {
+            functions: vec![nested.into_parametric(), s.clone()],
+        };
+        let expected = concat.linspace(20);
+        let compiled = compile(concat);
+
+        for (a, b) in compiled.linspace(20).into_iter().zip(expected) {
+            assert_relative_eq!(a.x, b.x, epsilon = 1e-4);
+            assert_relative_eq!(a.y, b.y, epsilon = 1e-4);
+        }
+
+        // nothing to collapse
+        assert!(s.optimize().is_none());
+        assert!(Rc::ptr_eq(&compile(s.clone()), &s));
+    }
+
+    #[test]
+    fn test_compile_through_wrappers() {
+        use crate::{
+            arclength::ConstantSpeed,
+            displace::{Jitter, ModulateNormal},
+            offset::Offset,
+            warp::WarpAlong,
+        };
+
+        let inner = || {
+            Translate::new(
+                Rotate::new(
+                    Segment::new((0.0, 0.0).into(), (1.0, 2.0).into()),
+                    (0.0, 1.0).into(),
+                    T::new(0.1),
+                ),
+                (3.0, -1.0).into(),
+            )
+        };
+        let wrappers: Vec<Rc<Box<dyn ParametricFunction2D>>> = vec![
+            EaseParam::new(inner(), |t: T| t.value() * t.value()).into_parametric(),
+            Map::new(inner(), |p: Point| p * 2.0).into_parametric(),
+            Shift::new(inner(), 0.2, true).into_parametric(),
+            ConstantSpeed::new(inner(), 1e-4).into_parametric(),
+            Offset::new(inner(), 0.5).into_parametric(),
+            WarpAlong::new(inner(), inner(), 1e-4).into_parametric(),
+            ModulateNormal::new(inner(), |t: T| t.value()).into_parametric(),
+            Jitter::new(inner(), 0.1, 7).into_parametric(),
+        ];
+
+        for wrapper in wrappers {
+            let compiled = compile(wrapper.clone());
+            assert!(!compiled.describe().contains("Translate"));
+            assert!(compiled.describe().contains("Affine"));
+            // normals come from finite differences, so differ slightly once the transforms are collapsed
+            for (a, b) in compiled.linspace(20).into_iter().zip(wrapper.linspace(20)) {
+                assert_relative_eq!(a.x, b.x, epsilon = 1e-3);
+                assert_relative_eq!(a.y, b.y, epsilon = 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shear() {
+        let s = Segment::new((1.0, 1.0).into(), (1.0, 3.0).into());
+        let sheared = Shear::new(s, (1.0, 1.0).into(), 0.5, 0.0);
+
+        let res = sheared.start();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = sheared.end();
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 3.0);
+
+        let s = Segment::new((0.0, 0.0).into(), (2.0, 0.0).into());
+        let sheared = Shear::new(s, (0.0, 0.0).into(), 0.0, -1.0);
+
+        let res = sheared.end();
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, -2.0);
+    }
 }
@@ -0,0 +1,192 @@
+//! Curves in 3D
+
+use std::f32::consts::TAU;
+
+use crate::{
+    angle::Angle,
+    core::{ParametricFunction3D, Point3, Vector3, T},
+};
+
+/// A line segment in 3D from a start point to an end point
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment3 {
+    pub start: Point3,
+    pub end: Point3,
+}
+
+impl Segment3 {
+    pub fn new(start: Point3, end: Point3) -> Self {
+        Self { start, end }
+    }
+}
+
+impl ParametricFunction3D for Segment3 {
+    fn evaluate(&self, t: T) -> Point3 {
+        self.start.lerp(self.end, t.value())
+    }
+
+    fn derivative(&self, _t: T) -> Vector3 {
+        self.end - self.start
+    }
+}
+
+/// A circle of radius `radius` centred at a point, lying in the plane through it at right angles to `normal` - it
+/// goes anticlockwise looking back down the normal, starting `start_angle` round from the first of
+/// [`Circle3::axes`]. With the normal along the z-axis this is the [`crate::circle::Circle`] in the xy-plane. The
+/// normal is fixed by [`Circle3::new`], as the axes are worked out from it there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circle3 {
+    pub centre: Point3,
+    pub radius: f32,
+    pub start_angle: Angle,
+    normal: Vector3,
+    axes: (Vector3, Vector3),
+}
+
+impl Circle3 {
+    pub fn new(centre: Point3, normal: Vector3, radius: f32, start_angle: Option<Angle>) -> Self {
+        let unit = normal
+            .try_normalize()
+            .unwrap_or(Vector3::new(0.0, 0.0, 1.0));
+        let axis = if unit.x.abs() < 0.9 {
+            Vector3::new(1.0, 0.0, 0.0)
+        } else {
+            Vector3::new(0.0, 1.0, 0.0)
+        };
+        let u = (axis - unit * axis.dot(unit)).normalize();
+
+        Self {
+            centre,
+            radius,
+            start_angle: start_angle.unwrap_or_default(),
+            normal,
+            axes: (u, unit.cross(u)),
+        }
+    }
+
+    /// returns the normal the circle was made with
+    pub fn normal(&self) -> Vector3 {
+        self.normal
+    }
+
+    /// returns a pair of unit vectors at right angles to each other spanning the plane of the circle - the first is
+    /// whichever of the x and y axes is further from the normal, flattened into the plane. A zero normal is taken
+    /// to be the z-axis.
+    pub fn axes(&self) -> (Vector3, Vector3) {
+        self.axes
+    }
+}
+
+impl ParametricFunction3D for Circle3 {
+    fn evaluate(&self, t: T) -> Point3 {
+        let (u, v) = self.axes;
+        let (sin, cos) = (self.start_angle + Angle::turns(t.value())).sin_cos();
+        self.centre + (u * cos + v * sin) * self.radius
+    }
+}
+
+/// A helix of radius `radius` winding `turns` times anticlockwise round the vertical line through `centre`, rising
+/// by `pitch` with each turn - it starts on the positive x side of `centre`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Helix {
+    pub centre: Point3,
+    pub radius: f32,
+    pub pitch: f32,
+    pub turns: f32,
+}
+
+impl Helix {
+    pub fn new(centre: Point3, radius: f32, pitch: f32, turns: f32) -> Self {
+        Self {
+            centre,
+            radius,
+            pitch,
+            turns,
+        }
+    }
+
+    /// returns the total height the helix rises through
+    pub fn height(&self) -> f32 {
+        self.pitch * self.turns
+    }
+
+    /// returns the length of the helix
+    pub fn length(&self) -> f32 {
+        self.turns.abs() * (TAU * self.radius).hypot(self.pitch)
+    }
+}
+
+impl ParametricFunction3D for Helix {
+    fn evaluate(&self, t: T) -> Point3 {
+        let turns = self.turns * t.value();
+        let (sin, cos) = (turns * TAU).sin_cos();
+        self.centre + Vector3::new(self.radius * cos, self.radius * sin, self.pitch * turns)
+    }
+
+    fn derivative(&self, t: T) -> Vector3 {
+        let (sin, cos) = (self.turns * t.value() * TAU).sin_cos();
+        let speed = self.turns * TAU * self.radius;
+        Vector3::new(-speed * sin, speed * cos, self.height())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_segment3() {
+        let s = Segment3::new((0.0, 0.0, 0.0).into(), (2.0, 4.0, -2.0).into());
+
+        let res = s.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 2.0);
+        assert_relative_eq!(res.z, -1.0);
+        assert_relative_eq!(s.arc_length(10), 24.0_f32.sqrt(), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_circle3() {
+        let c = Circle3::new(
+            (0.0, 0.0, 1.0).into(),
+            Vector3::new(0.0, 0.0, 2.0),
+            1.0,
+            None,
+        );
+
+        let res = c.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.z, 1.0, epsilon = 1e-6);
+
+        let c = Circle3::new(
+            (1.0, 2.0, 3.0).into(),
+            Vector3::new(1.0, 1.0, 1.0),
+            2.0,
+            None,
+        );
+        for t in T::range(8) {
+            let offset = c.evaluate(t) - c.centre;
+            assert_relative_eq!(offset.length(), 2.0, epsilon = 1e-5);
+            assert_relative_eq!(offset.dot(c.normal()), 0.0, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_helix() {
+        let h = Helix::new((0.0, 0.0, 0.0).into(), 1.0, 2.0, 3.0);
+
+        let res = h.end();
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(res.z, 6.0);
+
+        let res = h.evaluate(T::new(0.25 / 3.0));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.z, 0.5, epsilon = 1e-5);
+
+        assert_relative_eq!(h.arc_length(1000), h.length(), epsilon = 1e-2);
+    }
+}
@@ -0,0 +1,232 @@
+//! Roughening curves by displacing their points
+
+use std::rc::Rc;
+
+#[cfg(feature = "noise")]
+use noise::{NoiseFn, Perlin, Seedable};
+
+use crate::core::{IntoParametric2D, ParametricFunction1D, ParametricFunction2D, Point, Vector, T};
+
+/// A thing that implements [`ParametricFunction2D`] displaced along its [`ParametricFunction2D::normal`] by
+/// `amplitude(t)` - positive amplitudes are to the left of the direction of travel and negative ones to the right
+#[derive(Clone)]
+pub struct ModulateNormal {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub amplitude: Rc<Box<dyn ParametricFunction1D>>,
+}
+
+impl ModulateNormal {
+    pub fn new(
+        function: impl IntoParametric2D,
+        amplitude: impl ParametricFunction1D + 'static,
+    ) -> Self {
+        Self {
+            function: function.into_parametric(),
+            amplitude: Rc::new(Box::new(amplitude)),
+        }
+    }
+}
+
+impl ParametricFunction2D for ModulateNormal {
+    fn evaluate(&self, t: T) -> Point {
+        self.function.evaluate(t) + self.function.normal(t) * self.amplitude.evaluate(t)
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(ModulateNormal {
+            function: self.function.optimize()?,
+            amplitude: self.amplitude.clone(),
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("ModulateNormal({})", self.function.describe())
+    }
+}
+
+/// A thing that implements [`ParametricFunction2D`] displaced along its [`ParametricFunction2D::normal`] by smooth
+/// Perlin noise, for a hand drawn wobble. The displacement is at most roughly `amplitude` either side, `frequency` is
+/// the number of noise features per unit of `t`, and different `seed`s give different wobbles. Enabled by the `noise`
+/// feature.
+#[cfg(feature = "noise")]
+#[derive(Clone)]
+pub struct NoiseDisplace {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub amplitude: f32,
+    pub frequency: f32,
+    noise: Perlin,
+}
+
+#[cfg(feature = "noise")]
+impl NoiseDisplace {
+    pub fn new(function: impl IntoParametric2D, amplitude: f32, frequency: f32, seed: u32) -> Self {
+        Self {
+            function: function.into_parametric(),
+            amplitude,
+            frequency,
+            noise: Perlin::new(seed),
+        }
+    }
+
+    /// returns the seed of the noise
+    pub fn seed(&self) -> u32 {
+        self.noise.seed()
+    }
+}
+
+#[cfg(feature = "noise")]
+impl ParametricFunction2D for NoiseDisplace {
+    fn evaluate(&self, t: T) -> Point {
+        // along a row off the integer lattice, where Perlin noise is always zero - and off the halfway line too, where
+        // the diagonal gradients can cancel
+        let displacement = self.noise.get([(t.value() * self.frequency) as f64, 0.37]) as f32;
+        self.function.evaluate(t) + self.function.normal(t) * displacement * self.amplitude
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(NoiseDisplace {
+            function: self.function.optimize()?,
+            ..self.clone()
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "NoiseDisplace({}, {})",
+            self.function.describe(),
+            self.amplitude
+        )
+    }
+}
+
+/// A thing that implements [`ParametricFunction2D`] with each point knocked up to `amplitude` away in a random
+/// direction, for a rough or stippled look. Unlike `NoiseDisplace` neighbouring values of `t` are displaced
+/// independently, but the same `seed` and `t` always give the same displacement.
+#[derive(Clone)]
+pub struct Jitter {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub amplitude: f32,
+    pub seed: u64,
+}
+
+impl Jitter {
+    pub fn new(function: impl IntoParametric2D, amplitude: f32, seed: u64) -> Self {
+        Self {
+            function: function.into_parametric(),
+            amplitude,
+            seed,
+        }
+    }
+}
+
+impl ParametricFunction2D for Jitter {
+    fn evaluate(&self, t: T) -> Point {
+        let hash = split_mix(self.seed ^ split_mix(t.value().to_bits() as u64));
+
+        // a uniformly distributed point in the unit disc
+        let angle = unit(hash as u32) * std::f32::consts::TAU;
+        let radius = unit((hash >> 32) as u32).sqrt();
+        let (sin, cos) = angle.sin_cos();
+
+        self.function.evaluate(t) + Vector::new(cos, sin) * radius * self.amplitude
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(Jitter {
+            function: self.function.optimize()?,
+            amplitude: self.amplitude,
+            seed: self.seed,
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("Jitter({}, {})", self.function.describe(), self.amplitude)
+    }
+}
+
+/// the SplitMix64 finaliser, scrambling `x` into a well mixed hash
+fn split_mix(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// maps `bits` onto `0.0..1.0`
+fn unit(bits: u32) -> f32 {
+    (bits >> 8) as f32 / (1 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segment::Segment;
+    use approx::assert_relative_eq;
+
+    #[cfg(feature = "noise")]
+    #[test]
+    fn test_noise_displace() {
+        let s = Segment::new((0.0, 0.0).into(), (10.0, 0.0).into());
+        let n = NoiseDisplace::new(s, 0.5, 8.0, 42);
+        assert_eq!(n.seed(), 42);
+
+        let points = n.linspace(200);
+        assert!(points.iter().all(|p| p.y.abs() <= 0.5));
+        assert!(points.iter().any(|p| p.y.abs() > 0.05));
+
+        // reproducible, and only ever displaced along the normal
+        let again = NoiseDisplace::new(n.function.clone(), 0.5, 8.0, 42);
+        for (a, b) in points.iter().zip(again.linspace(200)) {
+            assert_relative_eq!(a.x, b.x);
+            assert_relative_eq!(a.y, b.y);
+        }
+        assert_relative_eq!(points[100].x, 5.0, epsilon = 1e-4);
+
+        // still displaced where `t * frequency` is a whole number
+        let at = T::new(1.0 / 8.0);
+        let ys: Vec<f32> = (0..4)
+            .map(|seed| {
+                NoiseDisplace::new(n.function.clone(), 0.5, 8.0, seed)
+                    .evaluate(at)
+                    .y
+            })
+            .collect();
+        assert!(ys.iter().any(|y| y.abs() > 1e-3));
+        assert!(ys.windows(2).any(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    fn test_modulate_normal() {
+        let s = Segment::new((0.0, 0.0).into(), (4.0, 0.0).into());
+        let wave = ModulateNormal::new(s, |t: T| 0.5 * f32::sin(t.value() * std::f32::consts::TAU));
+
+        let res = wave.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.5, epsilon = 1e-5);
+
+        let res = wave.evaluate(T::new(0.75));
+        assert_relative_eq!(res.x, 3.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, -0.5, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_jitter() {
+        let s = Segment::new((0.0, 0.0).into(), (10.0, 0.0).into());
+        let j = Jitter::new(s, 0.5, 7);
+
+        let points = j.linspace(200);
+        for (p, q) in points.iter().zip(j.function.linspace(200)) {
+            assert!((*p - q).length() <= 0.5);
+        }
+        assert!(points.iter().any(|p| p.y.abs() > 0.1));
+
+        // reproducible, but not for a different seed
+        let res = j.evaluate(T::new(0.3));
+        let again = Jitter::new(j.function.clone(), 0.5, 7).evaluate(T::new(0.3));
+        assert_relative_eq!(res.x, again.x);
+        assert_relative_eq!(res.y, again.y);
+
+        let other = Jitter::new(j.function.clone(), 0.5, 8).evaluate(T::new(0.3));
+        assert!((res - other).length() > 1e-6);
+    }
+}
@@ -0,0 +1,282 @@
+//! The standard easing functions, for [`EaseParam`](crate::core::EaseParam) and animation timing
+
+use crate::core::{ParametricFunction1D, T};
+
+/// The shape of an [`Easing`], named as on <https://easings.net>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EasingCurve {
+    Quad,
+    Cubic,
+    Quart,
+    Quint,
+    Sine,
+    Expo,
+    Circ,
+    /// overshoots backwards before setting off
+    Back,
+    /// wobbles like a spring
+    Elastic,
+    /// bounces like a dropped ball
+    Bounce,
+}
+
+/// Which end(s) of an [`Easing`] its curve is applied at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EasingDirection {
+    /// starts slowly
+    In,
+    /// ends slowly
+    Out,
+    /// starts and ends slowly, with the curve squashed into each half
+    InOut,
+}
+
+/// An easing function, going from 0 at [`T::start`] to 1 at [`T::end`] along `curve` - some of the curves overshoot
+/// 0 or 1 along the way
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Easing {
+    pub curve: EasingCurve,
+    pub direction: EasingDirection,
+}
+
+impl Easing {
+    pub fn new(curve: EasingCurve, direction: EasingDirection) -> Self {
+        Self { curve, direction }
+    }
+
+    /// the easing starting slowly along `curve`
+    pub fn ease_in(curve: EasingCurve) -> Self {
+        Self::new(curve, EasingDirection::In)
+    }
+
+    /// the easing ending slowly along `curve`
+    pub fn ease_out(curve: EasingCurve) -> Self {
+        Self::new(curve, EasingDirection::Out)
+    }
+
+    /// the easing starting and ending slowly along `curve`
+    pub fn ease_in_out(curve: EasingCurve) -> Self {
+        Self::new(curve, EasingDirection::InOut)
+    }
+}
+
+impl ParametricFunction1D for Easing {
+    fn evaluate(&self, t: T) -> f32 {
+        let x = t.value();
+        let ease_in = |x: f32| ease_in(self.curve, x);
+        match self.direction {
+            EasingDirection::In => ease_in(x),
+            EasingDirection::Out => 1.0 - ease_in(1.0 - x),
+            EasingDirection::InOut if x < 0.5 => ease_in(2.0 * x) / 2.0,
+            EasingDirection::InOut => 1.0 - ease_in(2.0 - 2.0 * x) / 2.0,
+        }
+    }
+}
+
+/// An easing following a cubic Bezier from (0, 0) to (1, 1) with controls at (`x1`, `y1`) and (`x2`, `y2`), as CSS
+/// `cubic-bezier()` timing functions do - the input is the x coordinate along it and the output the y coordinate
+/// there. As in CSS, `x1` and `x2` are clamped to 0 to 1 so there's only one y for each x.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezierEasing {
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+}
+
+impl CubicBezierEasing {
+    pub fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        Self { x1, y1, x2, y2 }
+    }
+
+    /// the CSS `ease` timing function
+    pub fn ease() -> Self {
+        Self::new(0.25, 0.1, 0.25, 1.0)
+    }
+
+    /// the CSS `ease-in` timing function
+    pub fn ease_in() -> Self {
+        Self::new(0.42, 0.0, 1.0, 1.0)
+    }
+
+    /// the CSS `ease-out` timing function
+    pub fn ease_out() -> Self {
+        Self::new(0.0, 0.0, 0.58, 1.0)
+    }
+
+    /// the CSS `ease-in-out` timing function
+    pub fn ease_in_out() -> Self {
+        Self::new(0.42, 0.0, 0.58, 1.0)
+    }
+
+    /// returns the parameter along the Bezier where its x coordinate is `x`, by Newton's method falling back on
+    /// bisection
+    fn solve(&self, x: f32) -> f32 {
+        const EPSILON: f32 = 1e-6;
+        let (x1, x2) = (self.x1.clamp(0.0, 1.0), self.x2.clamp(0.0, 1.0));
+        let at = |s: f32| cubic(x1, x2, s);
+
+        let mut s = x;
+        for _ in 0..8 {
+            let error = at(s) - x;
+            if error.abs() < EPSILON {
+                return s;
+            }
+            let slope = 3.0 * (1.0 - s) * (1.0 - s) * x1
+                + 6.0 * (1.0 - s) * s * (x2 - x1)
+                + 3.0 * s * s * (1.0 - x2);
+            if slope.abs() < EPSILON {
+                break;
+            }
+            s -= error / slope;
+        }
+
+        // x only ever grows along the curve, so this always closes in on it
+        let (mut low, mut high) = (0.0, 1.0);
+        s = x;
+        for _ in 0..50 {
+            let value = at(s);
+            if (value - x).abs() < EPSILON {
+                break;
+            }
+            if value < x {
+                low = s;
+            } else {
+                high = s;
+            }
+            s = (low + high) / 2.0;
+        }
+        s
+    }
+}
+
+impl ParametricFunction1D for CubicBezierEasing {
+    fn evaluate(&self, t: T) -> f32 {
+        let s = self.solve(t.value());
+        cubic(self.y1, self.y2, s)
+    }
+}
+
+/// a coordinate of the cubic Bezier from 0 to 1 through the controls `a` and `b`, at `s`
+fn cubic(a: f32, b: f32, s: f32) -> f32 {
+    let u = 1.0 - s;
+    3.0 * u * u * s * a + 3.0 * u * s * s * b + s * s * s
+}
+
+/// the "in" version of `curve` at `x`, from which the others follow
+fn ease_in(curve: EasingCurve, x: f32) -> f32 {
+    use std::f32::consts::{FRAC_PI_2, TAU};
+    const BACK: f32 = 1.70158;
+
+    match curve {
+        EasingCurve::Quad => x.powi(2),
+        EasingCurve::Cubic => x.powi(3),
+        EasingCurve::Quart => x.powi(4),
+        EasingCurve::Quint => x.powi(5),
+        EasingCurve::Sine => 1.0 - (x * FRAC_PI_2).cos(),
+        EasingCurve::Expo if x <= 0.0 => 0.0,
+        EasingCurve::Expo => 2f32.powf(10.0 * x - 10.0),
+        EasingCurve::Circ => 1.0 - (1.0 - x * x).max(0.0).sqrt(),
+        EasingCurve::Back => (BACK + 1.0) * x.powi(3) - BACK * x.powi(2),
+        EasingCurve::Elastic if x <= 0.0 || x >= 1.0 => x,
+        EasingCurve::Elastic => {
+            -(2f32.powf(10.0 * x - 10.0)) * ((10.0 * x - 10.75) * TAU / 3.0).sin()
+        }
+        EasingCurve::Bounce => 1.0 - bounce_out(1.0 - x),
+    }
+}
+
+/// a ball dropped from 1 at the start bouncing three times on the way to resting at 1
+fn bounce_out(x: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if x < 1.0 / D {
+        N * x * x
+    } else if x < 2.0 / D {
+        let x = x - 1.5 / D;
+        N * x * x + 0.75
+    } else if x < 2.5 / D {
+        let x = x - 2.25 / D;
+        N * x * x + 0.9375
+    } else {
+        let x = x - 2.625 / D;
+        N * x * x + 0.984375
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_easing() {
+        let curves = [
+            EasingCurve::Quad,
+            EasingCurve::Cubic,
+            EasingCurve::Quart,
+            EasingCurve::Quint,
+            EasingCurve::Sine,
+            EasingCurve::Expo,
+            EasingCurve::Circ,
+            EasingCurve::Back,
+            EasingCurve::Elastic,
+            EasingCurve::Bounce,
+        ];
+        for curve in curves {
+            for easing in [
+                Easing::ease_in(curve),
+                Easing::ease_out(curve),
+                Easing::ease_in_out(curve),
+            ] {
+                assert_relative_eq!(easing.start(), 0.0, epsilon = 1e-3);
+                assert_relative_eq!(easing.end(), 1.0, epsilon = 1e-3);
+            }
+            // in-out passes through the middle
+            let res = Easing::ease_in_out(curve).evaluate(T::new(0.5));
+            assert_relative_eq!(res, 0.5, epsilon = 1e-3);
+        }
+
+        let res = Easing::ease_in(EasingCurve::Cubic).evaluate(T::new(0.5));
+        assert_relative_eq!(res, 0.125);
+        let res = Easing::ease_out(EasingCurve::Quad).evaluate(T::new(0.5));
+        assert_relative_eq!(res, 0.75);
+        let res = Easing::ease_in_out(EasingCurve::Quad).evaluate(T::new(0.25));
+        assert_relative_eq!(res, 0.125);
+        let res = Easing::ease_in(EasingCurve::Sine).evaluate(T::new(0.5));
+        assert_relative_eq!(res, 1.0 - f32::sqrt(0.5));
+
+        // back dips below zero and elastic overshoots one
+        assert!(Easing::ease_in(EasingCurve::Back).evaluate(T::new(0.2)) < 0.0);
+        assert!(Easing::ease_out(EasingCurve::Elastic).evaluate(T::new(0.1)) > 1.0);
+        let res = Easing::ease_out(EasingCurve::Bounce).evaluate(T::new(1.0 / 2.75));
+        assert_relative_eq!(res, 1.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_cubic_bezier_easing() {
+        // with the controls on the diagonal it does nothing
+        let linear = CubicBezierEasing::new(0.25, 0.25, 0.75, 0.75);
+        for x in [0.0, 0.1, 0.5, 0.9, 1.0] {
+            assert_relative_eq!(linear.evaluate(T::new(x)), x, epsilon = 1e-5);
+        }
+
+        // symmetric about the middle
+        let e = CubicBezierEasing::ease_in_out();
+        assert_relative_eq!(e.evaluate(T::new(0.5)), 0.5, epsilon = 1e-5);
+        let (a, b) = (e.evaluate(T::new(0.2)), e.evaluate(T::new(0.8)));
+        assert_relative_eq!(a + b, 1.0, epsilon = 1e-5);
+        assert!(a < 0.2);
+
+        // values as browsers give them
+        let e = CubicBezierEasing::ease();
+        assert_relative_eq!(e.evaluate(T::new(0.25)), 0.4085, epsilon = 1e-3);
+        assert_relative_eq!(e.evaluate(T::new(0.5)), 0.8024, epsilon = 1e-3);
+        assert_relative_eq!(e.end(), 1.0);
+
+        // overshooting in y is allowed
+        let e = CubicBezierEasing::new(0.5, -0.5, 0.5, 1.5);
+        assert!(e.evaluate(T::new(0.1)) < 0.0);
+    }
+}
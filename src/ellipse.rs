@@ -0,0 +1,218 @@
+//! Elliptical arcs
+
+use crate::{
+    core::{ParametricFunction2D, Point, T},
+    error::{check_finite, check_points, check_radius, ParametricsError},
+};
+
+/// An arc of an ellipse centred at a point, with radii `radius_x` and `radius_y` along its axes and the axes turned
+/// anticlockwise by `rotation` - parameterisation runs from the "angle" `start_angle` to `end_angle`. Both are in
+/// "turns" as described in [`crate::circle::Circle`] but aren't clamped, so arcs can run in either direction and
+/// past the positive x-axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EllipseArc {
+    pub centre: Point,
+    pub radius_x: f32,
+    pub radius_y: f32,
+    pub rotation: f32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+}
+
+impl EllipseArc {
+    pub fn new(
+        centre: Point,
+        radius_x: f32,
+        radius_y: f32,
+        rotation: f32,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> Self {
+        Self {
+            centre,
+            radius_x,
+            radius_y,
+            rotation,
+            start_angle,
+            end_angle,
+        }
+    }
+
+    /// as [`EllipseArc::new`] but fails if anything isn't finite or either radius isn't above zero
+    pub fn try_new(
+        centre: Point,
+        radius_x: f32,
+        radius_y: f32,
+        rotation: f32,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> Result<Self, ParametricsError> {
+        check_points(&[centre])?;
+        check_radius(radius_x)?;
+        check_radius(radius_y)?;
+        check_finite([rotation, start_angle, end_angle])?;
+        Ok(Self::new(
+            centre,
+            radius_x,
+            radius_y,
+            rotation,
+            start_angle,
+            end_angle,
+        ))
+    }
+
+    /// the arc described by the arguments of an SVG path `A` command going from `start` to `end` - `x_rotation` is
+    /// in degrees as it is in SVG. Radii too small to reach are scaled up as the SVG spec says. Returns `None` when
+    /// the spec says the arc is left out (`start == end`) or drawn as a straight line (either radius is zero).
+    pub fn from_svg(
+        start: Point,
+        end: Point,
+        rx: f32,
+        ry: f32,
+        x_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+    ) -> Option<Self> {
+        if start == end || rx == 0.0 || ry == 0.0 {
+            return None;
+        }
+
+        // see "Conversion from endpoint to center parameterization" in the appendix of the SVG spec
+        let phi = x_rotation.to_radians();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+
+        let dx = (start.x - end.x) / 2.0;
+        let dy = (start.y - end.y) / 2.0;
+        let x1 = cos_phi * dx + sin_phi * dy;
+        let y1 = -sin_phi * dx + cos_phi * dy;
+
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let sign = if large_arc != sweep { 1.0 } else { -1.0 };
+        let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+        let cx1 = coefficient * rx * y1 / ry;
+        let cy1 = -coefficient * ry * x1 / rx;
+
+        let centre = Point::new(
+            cos_phi * cx1 - sin_phi * cy1 + (start.x + end.x) / 2.0,
+            sin_phi * cx1 + cos_phi * cy1 + (start.y + end.y) / 2.0,
+        );
+
+        let theta = f32::atan2((y1 - cy1) / ry, (x1 - cx1) / rx);
+        let end_theta = f32::atan2((-y1 - cy1) / ry, (-x1 - cx1) / rx);
+        let mut delta = (end_theta - theta).rem_euclid(std::f32::consts::TAU);
+        if !sweep && delta > 0.0 {
+            delta -= std::f32::consts::TAU;
+        }
+
+        let turns = |radians: f32| radians / std::f32::consts::TAU;
+        Some(Self::new(
+            centre,
+            rx,
+            ry,
+            turns(phi),
+            turns(theta),
+            turns(theta + delta),
+        ))
+    }
+}
+
+impl ParametricFunction2D for EllipseArc {
+    fn evaluate(&self, t: T) -> Point {
+        let theta = self.start_angle + (self.end_angle - self.start_angle) * t.value();
+        let (sin, cos) = (theta * std::f32::consts::TAU).sin_cos();
+        let (sin_rot, cos_rot) = (self.rotation * std::f32::consts::TAU).sin_cos();
+
+        let x = self.radius_x * cos;
+        let y = self.radius_y * sin;
+        (
+            self.centre.x + x * cos_rot - y * sin_rot,
+            self.centre.y + x * sin_rot + y * cos_rot,
+        )
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_ellipse_arc() {
+        let e = EllipseArc::new((1.0, 1.0).into(), 2.0, 1.0, 0.25, 0.0, 0.5);
+
+        // the x-axis of the ellipse points up
+        let res = e.start();
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 3.0, epsilon = 1e-6);
+
+        let res = e.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        let res = e.end();
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, -1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_ellipse_arc_from_svg() {
+        let start = Point::new(0.0, 0.0);
+        let end = Point::new(2.0, 0.0);
+
+        let e = EllipseArc::from_svg(start, end, 1.0, 1.0, 0.0, false, true).unwrap();
+        assert_relative_eq!(e.centre.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(e.centre.y, 0.0, epsilon = 1e-6);
+        let res = e.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, -1.0, epsilon = 1e-5);
+
+        let e = EllipseArc::from_svg(start, end, 1.0, 1.0, 0.0, false, false).unwrap();
+        let res = e.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+
+        // too small radii are scaled up to just reach
+        let e = EllipseArc::from_svg(start, end, 0.5, 0.5, 0.0, false, true).unwrap();
+        assert_relative_eq!(e.radius_x, 1.0, epsilon = 1e-6);
+
+        // the large arc goes the long way round the other centre
+        let e = EllipseArc::from_svg(
+            (1.0, 0.0).into(),
+            (0.0, 1.0).into(),
+            1.0,
+            1.0,
+            0.0,
+            true,
+            true,
+        )
+        .unwrap();
+        assert_relative_eq!(e.centre.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(e.centre.y, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(e.end_angle - e.start_angle, 0.75, epsilon = 1e-5);
+        let res = e.end();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+
+        // rotated ellipses still hit both ends
+        let e =
+            EllipseArc::from_svg(start, (3.0, 1.0).into(), 3.0, 1.0, 30.0, true, false).unwrap();
+        let res = e.start();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-4);
+        let res = e.end();
+        assert_relative_eq!(res.x, 3.0, epsilon = 1e-4);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-4);
+
+        assert!(EllipseArc::from_svg(start, start, 1.0, 1.0, 0.0, false, true).is_none());
+        assert!(EllipseArc::from_svg(start, end, 0.0, 1.0, 0.0, false, true).is_none());
+    }
+}
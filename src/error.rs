@@ -0,0 +1,95 @@
+//! Errors
+
+use std::fmt;
+
+use crate::core::Point;
+
+/// Errors from constructing parametric functions
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParametricsError {
+    /// a combinator was given no functions to combine
+    Empty,
+    /// the end of function `index` and the start of function `index + 1` are `gap` apart
+    Discontinuous { index: usize, gap: f32 },
+    /// points that were meant to pick out a curve all lie on one line (or coincide)
+    Collinear,
+    /// a curve needed at least `needed` points but was only given `found`
+    TooFewPoints { needed: usize, found: usize },
+    /// `expected` values were needed to go one to one with some points, but `found` were given
+    LengthMismatch { expected: usize, found: usize },
+    /// a knot vector was the wrong length for its control points and degree, or went backwards somewhere
+    InvalidKnots,
+    /// a coordinate or some other value was NaN or infinite
+    NotFinite,
+    /// a radius was zero or negative
+    NonPositiveRadius { radius: f32 },
+    /// a curve that needs two distinct points was given the same point twice
+    ZeroLength,
+    /// a combinator needed a count of at least one (repeats, sides) but was given zero
+    ZeroCount,
+    /// weights were negative or none of them were above zero
+    InvalidWeights,
+}
+
+impl fmt::Display for ParametricsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParametricsError::Empty => write!(f, "no functions were given"),
+            ParametricsError::Discontinuous { index, gap } => write!(
+                f,
+                "function {} ends {} away from the start of function {}",
+                index,
+                gap,
+                index + 1
+            ),
+            ParametricsError::Collinear => write!(f, "the points are collinear"),
+            ParametricsError::TooFewPoints { needed, found } => write!(
+                f,
+                "at least {} points are needed but only {} were given",
+                needed, found
+            ),
+            ParametricsError::LengthMismatch { expected, found } => write!(
+                f,
+                "{} values were needed to match the points but {} were given",
+                expected, found
+            ),
+            ParametricsError::InvalidKnots => write!(f, "the knot vector is invalid"),
+            ParametricsError::NotFinite => write!(f, "a value was NaN or infinite"),
+            ParametricsError::NonPositiveRadius { radius } => {
+                write!(f, "the radius {} is not above zero", radius)
+            }
+            ParametricsError::ZeroLength => write!(f, "the start and end are the same point"),
+            ParametricsError::ZeroCount => write!(f, "a count of zero was given"),
+            ParametricsError::InvalidWeights => {
+                write!(f, "the weights are negative or none are above zero")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParametricsError {}
+
+/// fails with [`ParametricsError::NotFinite`] if any of `values` is NaN or infinite
+pub(crate) fn check_finite(values: impl IntoIterator<Item = f32>) -> Result<(), ParametricsError> {
+    if values.into_iter().all(f32::is_finite) {
+        Ok(())
+    } else {
+        Err(ParametricsError::NotFinite)
+    }
+}
+
+/// fails with [`ParametricsError::NotFinite`] if any coordinate of `points` is NaN or infinite
+pub(crate) fn check_points<'a>(
+    points: impl IntoIterator<Item = &'a Point>,
+) -> Result<(), ParametricsError> {
+    check_finite(points.into_iter().flat_map(|p| [p.x, p.y]))
+}
+
+/// fails if `radius` is NaN, infinite, zero or negative
+pub(crate) fn check_radius(radius: f32) -> Result<(), ParametricsError> {
+    check_finite([radius])?;
+    if radius <= 0.0 {
+        return Err(ParametricsError::NonPositiveRadius { radius });
+    }
+    Ok(())
+}
@@ -0,0 +1,204 @@
+//! Chainable versions of the combinators
+
+use crate::angle::Angle;
+use crate::arclength::ConstantSpeed;
+use crate::displace::{Jitter, ModulateNormal};
+use crate::offset::Offset;
+use crate::ops::Curve;
+use crate::segment::Segment;
+use crate::warp::WarpAlong;
+use std::rc::Rc;
+
+use crate::core::{
+    compile, Axis, Component, Concat, EaseParam, IntoParametric2D, Map, Morph,
+    ParametricFunction1D, ParametricFunction2D, Point, ProjectOntoLine, Repeat, Reverse, Rotate,
+    Scale, Shear, Shift, SubCurve, Translate, T,
+};
+
+/// Extension trait wrapping things which implement [`IntoParametric2D`] in the combinators from [`crate::core`]
+///
+/// ```
+/// use parametrics::{core::ParametricFunction2D, ext::ParametricExt, Angle, Circle, Segment};
+///
+/// let curve = Segment::new((0.0, 0.0).into(), (1.0, 0.0).into())
+///     .then(Circle::with_angle((2.0, 0.0).into(), 1.0, Some(Angle::turns(0.5))))
+///     .rotated((0.0, 0.0).into(), Angle::degrees(90.0))
+///     .translated((1.0, 1.0).into());
+/// let points = curve.linspace(50);
+/// ```
+pub trait ParametricExt: IntoParametric2D + Sized {
+    /// see [`Curve`], for putting curves together with operators
+    fn curve(self) -> Curve {
+        Curve::new(self)
+    }
+
+    /// see [`Translate`]
+    fn translated(self, by: Point) -> Translate {
+        Translate::new(self, by)
+    }
+
+    /// see [`Rotate`]
+    fn rotated(self, centre: Point, angle: impl Into<Angle>) -> Rotate {
+        Rotate::new(self, centre, angle)
+    }
+
+    /// see [`Scale`]
+    fn scaled(self, centre: Point, scale_x: f32, scale_y: f32) -> Scale {
+        Scale::new(self, centre, scale_x, scale_y)
+    }
+
+    /// see [`Shear`]
+    fn sheared(self, centre: Point, shear_x: f32, shear_y: f32) -> Shear {
+        Shear::new(self, centre, shear_x, shear_y)
+    }
+
+    /// see [`Reverse`]
+    fn reversed(self) -> Reverse {
+        Reverse::new(self)
+    }
+
+    /// see [`Repeat`]
+    fn repeated(self, n: usize) -> Repeat {
+        Repeat::new(self, n)
+    }
+
+    /// the [`Concat`] of this followed by `other` - each gets half of `t`, so chaining several calls gives the
+    /// earlier ones less and less of it
+    fn then(self, other: impl IntoParametric2D) -> Concat {
+        Concat {
+            functions: vec![self.into_parametric(), other.into_parametric()],
+        }
+    }
+
+    /// see [`SubCurve`]
+    fn subcurve(self, start: T, end: T) -> SubCurve {
+        SubCurve::new(self, start, end)
+    }
+
+    /// see [`Shift`]
+    fn shifted(self, offset: f32, wrap: bool) -> Shift {
+        Shift::new(self, offset, wrap)
+    }
+
+    /// see [`ConstantSpeed`]
+    fn constant_speed(self, tolerance: f32) -> ConstantSpeed {
+        ConstantSpeed::new(self, tolerance)
+    }
+
+    /// the [`Morph`] from this to `other`
+    fn morphed(self, other: impl IntoParametric2D, amount: f32) -> Morph {
+        Morph::new(self, other, amount)
+    }
+
+    /// see [`ModulateNormal`]
+    fn modulated(self, amplitude: impl ParametricFunction1D + 'static) -> ModulateNormal {
+        ModulateNormal::new(self, amplitude)
+    }
+
+    /// see [`Offset`]
+    fn offset(self, distance: f32) -> Offset {
+        Offset::new(self, distance)
+    }
+
+    /// see [`ProjectOntoLine`]
+    fn projected_onto(self, line: Segment) -> ProjectOntoLine {
+        ProjectOntoLine::new(self, line)
+    }
+
+    /// see [`Jitter`]
+    fn jittered(self, amplitude: f32, seed: u64) -> Jitter {
+        Jitter::new(self, amplitude, seed)
+    }
+
+    /// see [`WarpAlong`] - this is the profile
+    fn warped_along(self, rail: impl IntoParametric2D, tolerance: f32) -> WarpAlong {
+        WarpAlong::new(self, rail, tolerance)
+    }
+
+    /// see [`compile`]
+    fn compiled(self) -> Rc<Box<dyn ParametricFunction2D>> {
+        compile(self)
+    }
+
+    /// see [`EaseParam`]
+    fn eased(self, easing: impl ParametricFunction1D + 'static) -> EaseParam {
+        EaseParam::new(self, easing)
+    }
+
+    /// see [`Map`]
+    fn mapped(self, f: impl Fn(Point) -> Point + 'static) -> Map {
+        Map::new(self, f)
+    }
+
+    /// the x coordinate, see [`Component`]
+    fn x_component(self) -> Component {
+        Component::new(self, Axis::X)
+    }
+
+    /// the y coordinate, see [`Component`]
+    fn y_component(self) -> Component {
+        Component::new(self, Axis::Y)
+    }
+}
+
+impl<P> ParametricExt for P where P: IntoParametric2D {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::ParametricFunction2D, segment::Segment};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_chained() {
+        let s = Segment::new((0.0, 0.0).into(), (1.0, 0.0).into());
+        let c = s
+            .translated((1.0, 0.0).into())
+            .rotated((0.0, 0.0).into(), T::new(0.25))
+            .scaled((0.0, 0.0).into(), 1.0, 2.0);
+
+        let res = c.start();
+        assert_relative_eq!(res.x, 0.0, epsilon = f32::EPSILON * 10.0);
+        assert_relative_eq!(res.y, 2.0, epsilon = f32::EPSILON * 10.0);
+
+        let res = c.end();
+        assert_relative_eq!(res.x, 0.0, epsilon = f32::EPSILON * 10.0);
+        assert_relative_eq!(res.y, 4.0, epsilon = f32::EPSILON * 10.0);
+    }
+
+    #[test]
+    fn test_then_reversed_repeated() {
+        let s1 = Segment::new((0.0, 0.0).into(), (1.0, 0.0).into());
+        let s2 = Segment::new((1.0, 0.0).into(), (1.0, 1.0).into());
+        let c = s1.then(s2).reversed().repeated(2);
+
+        let res = c.start();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = c.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.0);
+    }
+
+    #[test]
+    fn test_subcurve() {
+        let s = Segment::new((0.0, 0.0).into(), (4.0, 0.0).into());
+        let c = s.subcurve(T::new(0.25), T::new(0.5));
+
+        let res = c.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.5);
+        assert_relative_eq!(res.y, 0.0);
+    }
+
+    #[test]
+    fn test_components() {
+        let x = Segment::new((1.0, 0.0).into(), (3.0, -2.0).into()).x_component();
+        assert_relative_eq!(x.evaluate(T::new(0.5)), 2.0);
+        assert_relative_eq!(x.integral(T::start(), T::end()), 2.0, epsilon = 1e-5);
+
+        let y = x.function.clone().y_component();
+        assert_relative_eq!(y.end(), -2.0);
+        assert_relative_eq!(y.derivative(T::new(0.5)), -2.0, epsilon = 1e-3);
+    }
+}
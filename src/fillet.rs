@@ -0,0 +1,158 @@
+//! Smoothing the joins between concatenated curves
+
+use std::rc::Rc;
+
+use crate::{
+    bezier::BezierThird,
+    core::{Concat, IntoParametric2D, ParametricFunction2D, Point, SubCurve, T},
+    error::ParametricsError,
+};
+
+/// How far apart consecutive unit tangents have to be for a join to count as a kink
+const KINK_TOLERANCE: f32 = 1e-3;
+
+/// The [`Concat`] of the `functions` with a cubic [`BezierThird`] blend inserted at each kink, giving a G1
+/// continuous path. A kink is a join where one function ends within `tolerance` of where the next starts but
+/// their tangents disagree - the functions either side are trimmed back by (up to) `radius` and the blend
+/// bridges the gap. Joins which don't meet are left as they are.
+pub fn fillet_join(
+    functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
+    radius: f32,
+    tolerance: f32,
+) -> Result<Concat, ParametricsError> {
+    if functions.is_empty() {
+        return Err(ParametricsError::Empty);
+    }
+
+    let n = functions.len();
+
+    // the trimmed range of each function, and the blend (if any) following it
+    let mut ranges = vec![(T::start(), T::end()); n];
+    let mut blends: Vec<Option<BezierThird>> = vec![];
+
+    for i in 0..n - 1 {
+        let (before, after) = (&functions[i], &functions[i + 1]);
+        let join = after.start();
+
+        let meets = (join - before.end()).length() <= tolerance;
+        let kinked =
+            (after.tangent(T::start()) - before.tangent(T::end())).length() > KINK_TOLERANCE;
+        if !meets || !kinked {
+            blends.push(None);
+            continue;
+        }
+
+        // never trim more than half of either function away
+        let r = radius
+            .min((before.start() - join).length() / 2.0)
+            .min((after.end() - join).length() / 2.0);
+        if r <= 0.0 {
+            blends.push(None);
+            continue;
+        }
+
+        let end_t = trim(before.as_ref().as_ref(), join, r, true);
+        let start_t = trim(after.as_ref().as_ref(), join, r, false);
+        ranges[i].1 = end_t;
+        ranges[i + 1].0 = start_t;
+
+        let a = before.evaluate(end_t);
+        let b = after.evaluate(start_t);
+        let reach = (b - a).length() / 3.0;
+
+        blends.push(Some(BezierThird::new(
+            a,
+            b,
+            a + before.tangent(end_t) * reach,
+            b - after.tangent(start_t) * reach,
+        )));
+    }
+    blends.push(None);
+
+    let mut joined = vec![];
+    for ((function, (start, end)), blend) in functions.into_iter().zip(ranges).zip(blends) {
+        if start == T::start() && end == T::end() {
+            joined.push(function);
+        } else {
+            joined.push(SubCurve::new(function, start, end).into_parametric());
+        }
+        if let Some(blend) = blend {
+            joined.push(blend.into_parametric());
+        }
+    }
+
+    Concat::new(joined)
+}
+
+/// the parameter at which `function` is `distance` from `join`, searching back from its end if `from_end` and
+/// forward from its start otherwise
+fn trim(function: &dyn ParametricFunction2D, join: Point, distance: f32, from_end: bool) -> T {
+    // far is a parameter known to be at least `distance` away from the join, near one known to be closer
+    let (mut far, mut near) = if from_end { (0.0, 1.0) } else { (1.0, 0.0) };
+    for _ in 0..32 {
+        let mid = (far + near) / 2.0;
+        if (function.evaluate(T::new(mid)) - join).length() >= distance {
+            far = mid;
+        } else {
+            near = mid;
+        }
+    }
+    T::new(far)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segment::Segment;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_fillet_join() {
+        let functions: Vec<Rc<Box<dyn ParametricFunction2D>>> = vec![
+            Rc::new(Box::new(Segment::new((0.0, 0.0).into(), (2.0, 0.0).into()))),
+            Rc::new(Box::new(Segment::new((2.0, 0.0).into(), (2.0, 2.0).into()))),
+        ];
+
+        let path = fillet_join(functions, 0.5, 1e-6).unwrap();
+        assert_eq!(path.functions.len(), 3);
+
+        let blend = &path.functions[1];
+        let res = blend.start();
+        assert_relative_eq!(res.x, 1.5, epsilon = 1e-4);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-4);
+
+        let res = blend.end();
+        assert_relative_eq!(res.x, 2.0, epsilon = 1e-4);
+        assert_relative_eq!(res.y, 0.5, epsilon = 1e-4);
+
+        // the tangents agree either side of both joins
+        let tangent = blend.tangent(T::start());
+        assert_relative_eq!(tangent.x, 1.0, epsilon = 1e-3);
+        assert_relative_eq!(tangent.y, 0.0, epsilon = 1e-3);
+
+        let tangent = blend.tangent(T::end());
+        assert_relative_eq!(tangent.x, 0.0, epsilon = 1e-3);
+        assert_relative_eq!(tangent.y, 1.0, epsilon = 1e-3);
+
+        let res = path.end();
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 2.0);
+    }
+
+    #[test]
+    fn test_fillet_join_leaves_smooth_and_open_joins() {
+        let functions: Vec<Rc<Box<dyn ParametricFunction2D>>> = vec![
+            Rc::new(Box::new(Segment::new((0.0, 0.0).into(), (1.0, 0.0).into()))),
+            Rc::new(Box::new(Segment::new((1.0, 0.0).into(), (2.0, 0.0).into()))),
+            Rc::new(Box::new(Segment::new((3.0, 0.0).into(), (3.0, 1.0).into()))),
+        ];
+
+        let path = fillet_join(functions, 0.5, 1e-6).unwrap();
+        assert_eq!(path.functions.len(), 3);
+
+        assert_eq!(
+            fillet_join(vec![], 0.5, 1e-6).err(),
+            Some(ParametricsError::Empty)
+        );
+    }
+}
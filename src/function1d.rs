@@ -0,0 +1,600 @@
+//! Building blocks for 1D parametric functions
+
+#[cfg(feature = "noise")]
+use noise::{NoiseFn, Perlin, Seedable};
+
+use crate::{
+    core::{locate, ParametricFunction1D, T},
+    error::ParametricsError,
+    wave::ZigZagKind,
+};
+
+/// The same `value` everywhere
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Constant {
+    pub value: f32,
+}
+
+impl Constant {
+    pub fn new(value: f32) -> Self {
+        Self { value }
+    }
+}
+
+impl ParametricFunction1D for Constant {
+    fn evaluate(&self, _t: T) -> f32 {
+        self.value
+    }
+}
+
+/// Straight from `from` at [`T::start`] to `to` at [`T::end`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Linear {
+    pub from: f32,
+    pub to: f32,
+}
+
+impl Linear {
+    pub fn new(from: f32, to: f32) -> Self {
+        Self { from, to }
+    }
+}
+
+impl ParametricFunction1D for Linear {
+    fn evaluate(&self, t: T) -> f32 {
+        self.from + (self.to - self.from) * t.value()
+    }
+}
+
+/// The polynomial in `t` with `coefficients` from the constant term up, so `[1.0, 0.0, 2.0]` is `1 + 2t^2` - no
+/// coefficients at all is zero everywhere
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial {
+    pub coefficients: Vec<f32>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<f32>) -> Self {
+        Self { coefficients }
+    }
+}
+
+impl ParametricFunction1D for Polynomial {
+    fn evaluate(&self, t: T) -> f32 {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(0.0, |sum, c| sum * t.value() + c)
+    }
+}
+
+/// A sine wave going through `frequency` cycles, swinging `amplitude` either side of `offset` - `phase` shifts it
+/// along, in cycles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sine1D {
+    pub frequency: f32,
+    pub amplitude: f32,
+    pub phase: f32,
+    pub offset: f32,
+}
+
+impl Sine1D {
+    pub fn new(frequency: f32, amplitude: f32, phase: f32, offset: f32) -> Self {
+        Self {
+            frequency,
+            amplitude,
+            phase,
+            offset,
+        }
+    }
+}
+
+impl ParametricFunction1D for Sine1D {
+    fn evaluate(&self, t: T) -> f32 {
+        let cycles = self.frequency * t.value() + self.phase;
+        self.offset + self.amplitude * (cycles * std::f32::consts::TAU).sin()
+    }
+}
+
+/// A piecewise linear wave as [`Sine1D`], with each cycle in the shape given by `kind` - these start from `offset`
+/// heading up, like the sine does
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wave1D {
+    pub frequency: f32,
+    pub amplitude: f32,
+    pub phase: f32,
+    pub offset: f32,
+    pub kind: ZigZagKind,
+}
+
+impl Wave1D {
+    /// a triangle wave
+    pub fn triangle(frequency: f32, amplitude: f32, phase: f32, offset: f32) -> Self {
+        Self {
+            frequency,
+            amplitude,
+            phase,
+            offset,
+            kind: ZigZagKind::Triangle,
+        }
+    }
+
+    /// a sawtooth wave
+    pub fn sawtooth(frequency: f32, amplitude: f32, phase: f32, offset: f32) -> Self {
+        Self {
+            kind: ZigZagKind::Sawtooth,
+            ..Self::triangle(frequency, amplitude, phase, offset)
+        }
+    }
+
+    /// a square wave
+    pub fn square(frequency: f32, amplitude: f32, phase: f32, offset: f32) -> Self {
+        Self {
+            kind: ZigZagKind::Square,
+            ..Self::triangle(frequency, amplitude, phase, offset)
+        }
+    }
+}
+
+impl ParametricFunction1D for Wave1D {
+    fn evaluate(&self, t: T) -> f32 {
+        let u = (self.frequency * t.value() + self.phase).rem_euclid(1.0);
+        let shape = match self.kind {
+            ZigZagKind::Triangle if u < 0.25 => 4.0 * u,
+            ZigZagKind::Triangle if u < 0.75 => 2.0 - 4.0 * u,
+            ZigZagKind::Triangle => 4.0 * u - 4.0,
+            ZigZagKind::Sawtooth if u < 0.5 => 2.0 * u,
+            ZigZagKind::Sawtooth => 2.0 * u - 2.0,
+            ZigZagKind::Square if u < 0.5 => 1.0,
+            ZigZagKind::Square => -1.0,
+        };
+        self.offset + self.amplitude * shape
+    }
+}
+
+/// Smooth Perlin noise between roughly -1 and 1, for organic wobbles in radii, widths and so on. `frequency` is the
+/// number of noise features per unit of `t`, each of the `octaves` after the first adds finer detail at twice the
+/// frequency and half the strength of the last, and different `seed`s give different noise. Enabled by the `noise`
+/// feature.
+#[cfg(feature = "noise")]
+pub struct Noise1D {
+    pub frequency: f32,
+    pub octaves: usize,
+    noise: Perlin,
+}
+
+#[cfg(feature = "noise")]
+impl Noise1D {
+    pub fn new(seed: u32, frequency: f32, octaves: usize) -> Self {
+        Self {
+            frequency,
+            octaves,
+            noise: Perlin::new(seed),
+        }
+    }
+
+    /// returns the seed of the noise
+    pub fn seed(&self) -> u32 {
+        self.noise.seed()
+    }
+}
+
+#[cfg(feature = "noise")]
+impl ParametricFunction1D for Noise1D {
+    fn evaluate(&self, t: T) -> f32 {
+        let (mut sum, mut total) = (0.0, 0.0);
+        let (mut frequency, mut weight) = (self.frequency as f64, 1.0);
+        for octave in 0..self.octaves.max(1) {
+            // Perlin noise is zero on its integer lattice, so each octave runs along its own row off it - and off the
+            // halfway line too, where the diagonal gradients can cancel
+            let x = t.value() as f64 * frequency;
+            sum += self.noise.get([x, octave as f64 + 0.37]) * weight;
+            total += weight;
+            frequency *= 2.0;
+            weight /= 2.0;
+        }
+        (sum / total) as f32
+    }
+}
+
+/// How a [`Step1D`] gets from one breakpoint to the next
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepKind {
+    /// holds each value until the next breakpoint
+    Hold,
+    /// goes straight from each value to the next
+    Linear,
+}
+
+/// A schedule of values keyed at `breakpoints` - pairs of where and what, in order of where - which is the first
+/// value before the first breakpoint, the last value after the last, and in between as given by `kind`. No
+/// breakpoints at all is zero everywhere.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step1D {
+    pub breakpoints: Vec<(T, f32)>,
+    pub kind: StepKind,
+}
+
+impl Step1D {
+    /// the piecewise constant schedule, with the `breakpoints` in any order
+    pub fn new(mut breakpoints: Vec<(T, f32)>) -> Self {
+        breakpoints.sort_by(|a, b| a.0.value().total_cmp(&b.0.value()));
+        Self {
+            breakpoints,
+            kind: StepKind::Hold,
+        }
+    }
+
+    /// the piecewise linear schedule, with the `breakpoints` in any order
+    pub fn linear(breakpoints: Vec<(T, f32)>) -> Self {
+        Self {
+            kind: StepKind::Linear,
+            ..Self::new(breakpoints)
+        }
+    }
+}
+
+impl ParametricFunction1D for Step1D {
+    fn evaluate(&self, t: T) -> f32 {
+        let next = self
+            .breakpoints
+            .partition_point(|(at, _)| at.value() <= t.value());
+        match (next, self.breakpoints.get(next)) {
+            (_, None) => self.breakpoints.last().map_or(0.0, |(_, value)| *value),
+            (0, Some((_, value))) => *value,
+            (next, Some(&(to_t, to))) => {
+                let (from_t, from) = self.breakpoints[next - 1];
+                match self.kind {
+                    StepKind::Hold => from,
+                    StepKind::Linear => {
+                        let s = (t.value() - from_t.value()) / (to_t.value() - from_t.value());
+                        from + (to - from) * s
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// An ADSR envelope - rising from 0 to 1 over the first `attack` of `t`, falling to the `sustain` level over the
+/// next `decay`, holding there and then falling back to 0 over the last `release`. The lengths are fractions of the
+/// whole, and are scaled down together if they add up to more than it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Envelope {
+    pub attack: f32,
+    pub decay: f32,
+    pub sustain: f32,
+    pub release: f32,
+}
+
+impl Envelope {
+    pub fn new(attack: f32, decay: f32, sustain: f32, release: f32) -> Self {
+        Self {
+            attack,
+            decay,
+            sustain,
+            release,
+        }
+    }
+}
+
+impl ParametricFunction1D for Envelope {
+    fn evaluate(&self, t: T) -> f32 {
+        let [attack, decay, release] = [self.attack, self.decay, self.release].map(|x| x.max(0.0));
+        let scale = (attack + decay + release).max(1.0);
+        let (attack, decay, release) = (attack / scale, decay / scale, release / scale);
+
+        let t = t.value();
+        if t < attack {
+            t / attack
+        } else if t < attack + decay {
+            1.0 - (1.0 - self.sustain) * (t - attack) / decay
+        } else if t < 1.0 - release || release <= 0.0 {
+            self.sustain
+        } else {
+            self.sustain * (1.0 - t) / release
+        }
+    }
+}
+
+/// The concatenation of multiple things that implement [`ParametricFunction1D`], each taking an equal share of `t`
+pub struct Concat1D {
+    pub functions: Vec<Box<dyn ParametricFunction1D>>,
+}
+
+impl Concat1D {
+    /// fails if there are no `functions`, which a [`Concat1D`] can't be evaluated without
+    pub fn new(functions: Vec<Box<dyn ParametricFunction1D>>) -> Result<Self, ParametricsError> {
+        if functions.is_empty() {
+            return Err(ParametricsError::Empty);
+        }
+        Ok(Self { functions })
+    }
+}
+
+impl ParametricFunction1D for Concat1D {
+    fn evaluate(&self, t: T) -> f32 {
+        match locate(self.functions.len(), t) {
+            Some((index, t)) => self.functions[index].evaluate(t),
+            None => 0.0,
+        }
+    }
+}
+
+/// The repetition `n` times of a thing that implements [`ParametricFunction1D`]
+pub struct Repeat1D {
+    pub function: Box<dyn ParametricFunction1D>,
+    pub n: usize,
+}
+
+impl Repeat1D {
+    pub fn new(function: impl ParametricFunction1D + 'static, n: usize) -> Self {
+        Self {
+            function: Box::new(function),
+            n,
+        }
+    }
+}
+
+impl ParametricFunction1D for Repeat1D {
+    fn evaluate(&self, t: T) -> f32 {
+        let (_, t) = locate(self.n.max(1), t).unwrap_or((0, t));
+        self.function.evaluate(t)
+    }
+}
+
+/// A thing that implements [`ParametricFunction1D`] with its values multiplied by `scale`
+pub struct Scale1D {
+    pub function: Box<dyn ParametricFunction1D>,
+    pub scale: f32,
+}
+
+impl Scale1D {
+    pub fn new(function: impl ParametricFunction1D + 'static, scale: f32) -> Self {
+        Self {
+            function: Box::new(function),
+            scale,
+        }
+    }
+}
+
+impl ParametricFunction1D for Scale1D {
+    fn evaluate(&self, t: T) -> f32 {
+        self.function.evaluate(t) * self.scale
+    }
+}
+
+/// A thing that implements [`ParametricFunction1D`] with `offset` added to its values
+pub struct Offset1D {
+    pub function: Box<dyn ParametricFunction1D>,
+    pub offset: f32,
+}
+
+impl Offset1D {
+    pub fn new(function: impl ParametricFunction1D + 'static, offset: f32) -> Self {
+        Self {
+            function: Box::new(function),
+            offset,
+        }
+    }
+}
+
+impl ParametricFunction1D for Offset1D {
+    fn evaluate(&self, t: T) -> f32 {
+        self.function.evaluate(t) + self.offset
+    }
+}
+
+/// A thing that implements [`ParametricFunction1D`] traversed backwards
+pub struct Reverse1D {
+    pub function: Box<dyn ParametricFunction1D>,
+}
+
+impl Reverse1D {
+    pub fn new(function: impl ParametricFunction1D + 'static) -> Self {
+        Self {
+            function: Box::new(function),
+        }
+    }
+}
+
+impl ParametricFunction1D for Reverse1D {
+    fn evaluate(&self, t: T) -> f32 {
+        self.function.evaluate(T::new(1.0 - t.value()))
+    }
+}
+
+/// `outer` evaluated at `inner(t)` - the result of `inner` is clamped into a [`T`], so easings can be chained this way
+pub struct Compose1D {
+    pub outer: Box<dyn ParametricFunction1D>,
+    pub inner: Box<dyn ParametricFunction1D>,
+}
+
+impl Compose1D {
+    pub fn new(
+        outer: impl ParametricFunction1D + 'static,
+        inner: impl ParametricFunction1D + 'static,
+    ) -> Self {
+        Self {
+            outer: Box::new(outer),
+            inner: Box::new(inner),
+        }
+    }
+}
+
+impl ParametricFunction1D for Compose1D {
+    fn evaluate(&self, t: T) -> f32 {
+        self.outer.evaluate(T::new(self.inner.evaluate(t)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_primitives() {
+        let c = Constant::new(2.5);
+        assert_relative_eq!(c.evaluate(T::new(0.3)), 2.5);
+
+        let l = Linear::new(1.0, -3.0);
+        assert_relative_eq!(l.start(), 1.0);
+        assert_relative_eq!(l.evaluate(T::new(0.25)), 0.0);
+        assert_relative_eq!(l.end(), -3.0);
+
+        let p = Polynomial::new(vec![1.0, -2.0, 0.0, 4.0]);
+        assert_relative_eq!(p.start(), 1.0);
+        assert_relative_eq!(p.evaluate(T::new(0.5)), 1.0 - 1.0 + 0.5);
+        assert_relative_eq!(p.end(), 3.0);
+        assert_relative_eq!(Polynomial::new(vec![]).evaluate(T::new(0.5)), 0.0);
+    }
+
+    #[test]
+    fn test_oscillators() {
+        let s = Sine1D::new(2.0, 3.0, 0.0, 1.0);
+        assert_relative_eq!(s.start(), 1.0);
+        assert_relative_eq!(s.evaluate(T::new(0.125)), 4.0);
+        assert_relative_eq!(s.evaluate(T::new(0.375)), -2.0, epsilon = 1e-5);
+
+        // a quarter cycle on makes it a cosine
+        let s = Sine1D::new(1.0, 1.0, 0.25, 0.0);
+        assert_relative_eq!(s.start(), 1.0);
+
+        let w = Wave1D::triangle(2.0, 2.0, 0.0, 0.0);
+        assert_relative_eq!(w.evaluate(T::new(0.125)), 2.0);
+        assert_relative_eq!(w.evaluate(T::new(0.25)), 0.0);
+        assert_relative_eq!(w.evaluate(T::new(0.375)), -2.0);
+        assert_relative_eq!(w.evaluate(T::new(0.4375)), -1.0);
+
+        let w = Wave1D::sawtooth(1.0, 1.0, 0.0, 0.0);
+        assert_relative_eq!(w.evaluate(T::new(0.25)), 0.5);
+        assert_relative_eq!(w.evaluate(T::new(0.75)), -0.5);
+
+        let w = Wave1D::square(1.0, 1.0, 0.0, 5.0);
+        assert_relative_eq!(w.evaluate(T::new(0.25)), 6.0);
+        assert_relative_eq!(w.evaluate(T::new(0.75)), 4.0);
+    }
+
+    #[test]
+    fn test_combinators() {
+        // up and back down again
+        let c = Concat1D::new(vec![
+            Box::new(Linear::new(0.0, 1.0)),
+            Box::new(Reverse1D::new(Linear::new(0.0, 1.0))),
+        ])
+        .unwrap();
+        assert_relative_eq!(c.start(), 0.0);
+        assert_relative_eq!(c.evaluate(T::new(0.25)), 0.5);
+        assert_relative_eq!(c.evaluate(T::new(0.5)), 1.0);
+        assert_relative_eq!(c.evaluate(T::new(0.75)), 0.5);
+        assert_relative_eq!(c.end(), 0.0);
+        assert!(Concat1D::new(vec![]).is_err());
+
+        let r = Repeat1D::new(Linear::new(0.0, 1.0), 4);
+        assert_relative_eq!(r.evaluate(T::new(0.125)), 0.5);
+        assert_relative_eq!(r.evaluate(T::new(0.625)), 0.5);
+        assert_relative_eq!(r.end(), 1.0);
+
+        let s = Offset1D::new(Scale1D::new(Linear::new(0.0, 1.0), 3.0), -1.0);
+        assert_relative_eq!(s.start(), -1.0);
+        assert_relative_eq!(s.end(), 2.0);
+    }
+
+    #[test]
+    fn test_compose() {
+        let c = Compose1D::new(Polynomial::new(vec![0.0, 0.0, 1.0]), Linear::new(0.5, 1.0));
+        assert_relative_eq!(c.start(), 0.25);
+        assert_relative_eq!(c.end(), 1.0);
+
+        // clamped going in
+        let c = Compose1D::new(Linear::new(1.0, 2.0), Linear::new(-1.0, 2.0));
+        assert_relative_eq!(c.start(), 1.0);
+        assert_relative_eq!(c.evaluate(T::new(0.5)), 1.5);
+        assert_relative_eq!(c.end(), 2.0);
+    }
+
+    #[test]
+    fn test_derivative_and_integral() {
+        let p = Polynomial::new(vec![1.0, 0.0, 3.0]);
+        assert_relative_eq!(p.derivative(T::new(0.5)), 3.0, epsilon = 1e-2);
+        assert_relative_eq!(p.derivative(T::start()), 0.0, epsilon = 1e-2);
+        assert_relative_eq!(p.derivative(T::end()), 6.0, epsilon = 1e-2);
+
+        assert_relative_eq!(p.integral(T::start(), T::end()), 2.0, epsilon = 1e-5);
+        assert_relative_eq!(p.integral(T::end(), T::start()), -2.0, epsilon = 1e-5);
+        assert_relative_eq!(p.integral(T::new(0.5), T::new(0.5)), 0.0);
+
+        // a whole cycle cancels out
+        let s = Sine1D::new(1.0, 1.0, 0.0, 0.0);
+        assert_relative_eq!(s.integral(T::start(), T::end()), 0.0, epsilon = 1e-5);
+        let res = s.integral(T::start(), T::new(0.5));
+        assert_relative_eq!(res, 1.0 / std::f32::consts::PI, epsilon = 1e-5);
+    }
+
+    #[cfg(feature = "noise")]
+    #[test]
+    fn test_noise() {
+        let n = Noise1D::new(7, 5.0, 3);
+        assert_eq!(n.seed(), 7);
+
+        let values = n.linspace(200);
+        assert!(values.iter().all(|v| v.abs() <= 1.0));
+        assert!(values.iter().any(|v| v.abs() > 0.05));
+
+        // reproducible, and smooth
+        for (a, b) in values.iter().zip(Noise1D::new(7, 5.0, 3).linspace(200)) {
+            assert_relative_eq!(*a, b);
+        }
+        assert!(values.windows(2).all(|w| (w[1] - w[0]).abs() < 0.2));
+
+        // not pinned to zero where `t * frequency` is a whole number
+        let at = T::new(1.0 / 5.0);
+        let values: Vec<f32> = (0..4)
+            .map(|seed| Noise1D::new(seed, 5.0, 1).evaluate(at))
+            .collect();
+        assert!(values.iter().any(|v| v.abs() > 1e-3));
+        assert!(values.windows(2).any(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    fn test_step() {
+        let breakpoints = vec![
+            (T::new(0.5), 3.0),
+            (T::new(0.25), 1.0),
+            (T::new(0.75), -1.0),
+        ];
+
+        let s = Step1D::new(breakpoints.clone());
+        assert_relative_eq!(s.start(), 1.0);
+        assert_relative_eq!(s.evaluate(T::new(0.25)), 1.0);
+        assert_relative_eq!(s.evaluate(T::new(0.4)), 1.0);
+        assert_relative_eq!(s.evaluate(T::new(0.5)), 3.0);
+        assert_relative_eq!(s.end(), -1.0);
+
+        let s = Step1D::linear(breakpoints);
+        assert_relative_eq!(s.start(), 1.0);
+        assert_relative_eq!(s.evaluate(T::new(0.375)), 2.0);
+        assert_relative_eq!(s.evaluate(T::new(0.625)), 1.0);
+        assert_relative_eq!(s.end(), -1.0);
+
+        assert_relative_eq!(Step1D::new(vec![]).evaluate(T::new(0.5)), 0.0);
+    }
+
+    #[test]
+    fn test_envelope() {
+        let e = Envelope::new(0.1, 0.2, 0.5, 0.4);
+        assert_relative_eq!(e.start(), 0.0);
+        assert_relative_eq!(e.evaluate(T::new(0.05)), 0.5);
+        assert_relative_eq!(e.evaluate(T::new(0.1)), 1.0);
+        assert_relative_eq!(e.evaluate(T::new(0.2)), 0.75);
+        assert_relative_eq!(e.evaluate(T::new(0.5)), 0.5);
+        assert_relative_eq!(e.evaluate(T::new(0.8)), 0.25, epsilon = 1e-6);
+        assert_relative_eq!(e.end(), 0.0);
+
+        // squeezed to fit, and with no release it stays sustained
+        let e = Envelope::new(1.0, 1.0, 0.5, 0.0);
+        assert_relative_eq!(e.evaluate(T::new(0.5)), 1.0);
+        assert_relative_eq!(e.end(), 0.5);
+    }
+}
@@ -0,0 +1,85 @@
+//! Crate internal geometry helpers
+
+use crate::core::Point;
+
+/// closest point to `p` on the polyline through `polyline`
+pub(crate) fn closest_on_polyline(p: Point, polyline: &[Point]) -> Point {
+    if polyline.len() == 1 {
+        return polyline[0];
+    }
+    polyline
+        .windows(2)
+        .map(|s| closest_on_segment(p, s[0], s[1]))
+        .min_by(|a, b| (p - *a).length().total_cmp(&(p - *b).length()))
+        .unwrap_or(p)
+}
+
+/// closest point to `p` on the segment from `a` to `b`
+pub(crate) fn closest_on_segment(p: Point, a: Point, b: Point) -> Point {
+    let ab = b - a;
+    let len_sq = ab.square_length();
+    if len_sq <= f32::EPSILON {
+        return a;
+    }
+    let s = ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0);
+    a + ab * s
+}
+
+/// shortest distance between the segments `a` to `b` and `c` to `d`
+pub(crate) fn segment_segment_distance(a: Point, b: Point, c: Point, d: Point) -> f32 {
+    if segments_intersect(a, b, c, d) {
+        return 0.0;
+    }
+    [
+        (a - closest_on_segment(a, c, d)).length(),
+        (b - closest_on_segment(b, c, d)).length(),
+        (c - closest_on_segment(c, a, b)).length(),
+        (d - closest_on_segment(d, a, b)).length(),
+    ]
+    .into_iter()
+    .fold(f32::INFINITY, f32::min)
+}
+
+/// whether the segments `a` to `b` and `c` to `d` properly cross each other
+pub(crate) fn segments_intersect(a: Point, b: Point, c: Point, d: Point) -> bool {
+    let d1 = (b - a).cross(c - a);
+    let d2 = (b - a).cross(d - a);
+    let d3 = (d - c).cross(a - c);
+    let d4 = (d - c).cross(b - c);
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+/// `x` as a fraction `(n, d)` in lowest terms with `d` up to `max_denominator`, if it's that close to one
+pub(crate) fn as_fraction(x: f32, max_denominator: u32) -> Option<(u32, u32)> {
+    (1..=max_denominator).find_map(|d| {
+        let n = (x.abs() * d as f32).round();
+        ((x.abs() - n / d as f32).abs() < 1e-4).then_some((n as u32, d))
+    })
+}
+
+/// the corners of the convex hull of `points`, anticlockwise from the lowest leftmost one
+pub(crate) fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // Andrew's monotone chain, the lower hull then the upper
+    let mut hull: Vec<Point> = vec![];
+    for pass in [sorted.clone(), sorted.into_iter().rev().collect()] {
+        let start = hull.len();
+        for p in pass {
+            while hull.len() >= start + 2
+                && (hull[hull.len() - 1] - hull[hull.len() - 2]).cross(p - hull[hull.len() - 2])
+                    <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop();
+    }
+    hull
+}
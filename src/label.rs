@@ -0,0 +1,181 @@
+//! Placing labels next to curves
+
+use std::rc::Rc;
+
+use crate::{
+    arclength::ArcLengthTable,
+    core::{ParametricFunction2D, Point, T},
+    segment::Segment,
+};
+
+/// Number of samples used for the distance queries against the scene
+const DISTANCE_SAMPLES: usize = 64;
+
+/// Parameters along the anchor curve which are tried in turn, the middle of the curve is preferred
+const ANCHOR_TS: [f32; 9] = [0.5, 0.375, 0.625, 0.25, 0.75, 0.125, 0.875, 0.0, 1.0];
+
+/// Where a label should go - `anchor` is the label position and `leader` joins the curve to the label
+pub struct LabelPlacement {
+    pub anchor: Point,
+    pub leader: Segment,
+}
+
+/// Finds a position for a label near `anchor_curve` which keeps at least `clearance` away from every curve in
+/// `scene` (and from `anchor_curve` itself). Positions along the curve are tried starting from its middle and at
+/// each of them the `preferred_offsets` are tried in order, the first position which is clear is returned.
+///
+/// Returns `None` if none of the candidate positions are clear.
+pub fn place_label(
+    scene: &[Rc<Box<dyn ParametricFunction2D>>],
+    anchor_curve: &dyn ParametricFunction2D,
+    preferred_offsets: &[Point],
+    clearance: f32,
+) -> Option<LabelPlacement> {
+    ANCHOR_TS.iter().find_map(|&t| {
+        let on_curve = anchor_curve.evaluate(T::new(t));
+
+        preferred_offsets.iter().find_map(|offset| {
+            let anchor: Point = (on_curve.x + offset.x, on_curve.y + offset.y).into();
+
+            let clear = anchor_curve.distance_to(anchor, DISTANCE_SAMPLES) >= clearance
+                && scene
+                    .iter()
+                    .all(|f| f.distance_to(anchor, DISTANCE_SAMPLES) >= clearance);
+
+            clear.then(|| LabelPlacement {
+                anchor,
+                leader: Segment::new(on_curve, anchor),
+            })
+        })
+    })
+}
+
+/// What [`text_on_path`] does with characters that run past the end of the path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overflow {
+    /// leaves them off
+    Clip,
+    /// squeezes all the characters up to fit
+    Squeeze,
+    /// carries on in a straight line from the end
+    Extend,
+}
+
+/// Where a character of [`text_on_path`] goes - `anchor` is the left end of its baseline, and it is turned
+/// anticlockwise by `angle` (in "turns")
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphPlacement {
+    pub anchor: Point,
+    pub angle: f32,
+}
+
+/// Lays characters out along `path` by arc length, starting `offset` along it - `advances` are the widths of the
+/// characters in order, and each is turned to follow the path at its middle. `tolerance` is for measuring the path,
+/// see [`ArcLengthTable::new`].
+pub fn text_on_path(
+    path: &dyn ParametricFunction2D,
+    advances: &[f32],
+    offset: f32,
+    overflow: Overflow,
+    tolerance: f32,
+) -> Vec<GlyphPlacement> {
+    let table = ArcLengthTable::new(path, tolerance);
+    let total = table.total();
+    let width: f32 = advances.iter().sum();
+    let scale = match overflow {
+        Overflow::Squeeze if offset + width > total && width > 0.0 => {
+            (total - offset).max(0.0) / width
+        }
+        _ => 1.0,
+    };
+
+    // the point and unit tangent at `distance` along, carrying on straight past either end
+    let at = |distance: f32| {
+        let t = table.t_at_length(distance);
+        let tangent = path.tangent(t);
+        let beyond = if distance < 0.0 {
+            distance
+        } else {
+            (distance - total).max(0.0)
+        };
+        (path.evaluate(t) + tangent * beyond, tangent)
+    };
+
+    let mut placements = vec![];
+    let mut distance = offset;
+    for advance in advances.iter().map(|a| a * scale) {
+        if overflow == Overflow::Clip && distance + advance > total {
+            break;
+        }
+        let (middle, tangent) = at(distance + advance / 2.0);
+        placements.push(GlyphPlacement {
+            anchor: middle - tangent * advance / 2.0,
+            angle: tangent.y.atan2(tangent.x) / std::f32::consts::TAU,
+        });
+        distance += advance;
+    }
+    placements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::Circle;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_place_label() {
+        let curve = Segment::new((0.0, 0.0).into(), (10.0, 0.0).into());
+        let offsets: Vec<Point> = vec![(0.0, 2.0).into(), (0.0, -2.0).into()];
+
+        let placement = place_label(&[], &curve, &offsets, 1.0).unwrap();
+        assert_relative_eq!(placement.anchor.x, 5.0);
+        assert_relative_eq!(placement.anchor.y, 2.0);
+        assert_relative_eq!(placement.leader.start.x, 5.0);
+        assert_relative_eq!(placement.leader.start.y, 0.0);
+
+        // something in the way above the middle of the curve
+        let scene: Vec<Rc<Box<dyn ParametricFunction2D>>> =
+            vec![Rc::new(Box::new(Circle::new((5.0, 2.0).into(), 0.5, None)))];
+        let placement = place_label(&scene, &curve, &offsets, 1.0).unwrap();
+        assert_relative_eq!(placement.anchor.x, 5.0);
+        assert_relative_eq!(placement.anchor.y, -2.0);
+    }
+
+    #[test]
+    fn test_place_label_no_room() {
+        let curve = Segment::new((0.0, 0.0).into(), (10.0, 0.0).into());
+        let offsets: Vec<Point> = vec![(0.0, 0.5).into()];
+
+        assert!(place_label(&[], &curve, &offsets, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_text_on_path() {
+        let path = Segment::new((0.0, 0.0).into(), (0.0, 4.0).into());
+        let advances = [1.0, 0.5, 1.0, 2.0];
+
+        let glyphs = text_on_path(&path, &advances, 0.5, Overflow::Clip, 1e-4);
+        assert_eq!(glyphs.len(), 3);
+        assert_relative_eq!(glyphs[0].anchor.y, 0.5, epsilon = 1e-4);
+        assert_relative_eq!(glyphs[1].anchor.y, 1.5, epsilon = 1e-4);
+        assert_relative_eq!(glyphs[2].anchor.y, 2.0, epsilon = 1e-4);
+        assert_relative_eq!(glyphs[0].angle, 0.25, epsilon = 1e-4);
+
+        let glyphs = text_on_path(&path, &advances, 0.5, Overflow::Extend, 1e-4);
+        assert_eq!(glyphs.len(), 4);
+        assert_relative_eq!(glyphs[3].anchor.y, 3.0, epsilon = 1e-4);
+        assert_relative_eq!(glyphs[3].anchor.x, 0.0, epsilon = 1e-4);
+
+        // 4.5 wide squeezed into 3.5
+        let glyphs = text_on_path(&path, &advances, 0.5, Overflow::Squeeze, 1e-4);
+        assert_eq!(glyphs.len(), 4);
+        assert_relative_eq!(glyphs[3].anchor.y, 0.5 + 2.5 * 3.5 / 4.5, epsilon = 1e-4);
+
+        // following the bend of a circle
+        let circle = crate::circle::Circle::new((0.0, 0.0).into(), 1.0, None);
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let glyphs = text_on_path(&circle, &[quarter, quarter], 0.0, Overflow::Clip, 1e-5);
+        assert_relative_eq!(glyphs[1].angle, -0.375, epsilon = 1e-3);
+    }
+}
@@ -0,0 +1,76 @@
+//! Lemniscates - figure-of-eight curves
+
+use crate::core::{ParametricFunction2D, Point, T};
+
+/// The lemniscate of Bernoulli around `centre` - a figure of eight reaching `scale` either side of the centre along
+/// its axis, which is turned anticlockwise by `rotation` (in "turns"). It starts at the tip of the right hand loop,
+/// goes round it anticlockwise, crosses the centre and goes round the other loop clockwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lemniscate {
+    pub centre: Point,
+    pub scale: f32,
+    pub rotation: f32,
+}
+
+impl Lemniscate {
+    pub fn bernoulli(centre: Point, scale: f32, rotation: f32) -> Self {
+        Self {
+            centre,
+            scale,
+            rotation,
+        }
+    }
+}
+
+impl ParametricFunction2D for Lemniscate {
+    fn evaluate(&self, t: T) -> Point {
+        let (sin, cos) = (t.value() * std::f32::consts::TAU).sin_cos();
+        let denominator = 1.0 + sin * sin;
+        let x = self.scale * cos / denominator;
+        let y = self.scale * sin * cos / denominator;
+
+        let (sin_rot, cos_rot) = (self.rotation * std::f32::consts::TAU).sin_cos();
+        (
+            self.centre.x + x * cos_rot - y * sin_rot,
+            self.centre.y + x * sin_rot + y * cos_rot,
+        )
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_lemniscate() {
+        let l = Lemniscate::bernoulli((1.0, 1.0).into(), 2.0, 0.0);
+
+        let res = l.start();
+        assert_relative_eq!(res.x, 3.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = l.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        let res = l.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, -1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        // the product of the distances to the foci is constant
+        let focus = f32::sqrt(2.0);
+        for p in l.linspace(20) {
+            let p = p - Point::new(1.0, 1.0);
+            let product =
+                (p - euclid::vec2(focus, 0.0)).length() * (p + euclid::vec2(focus, 0.0)).length();
+            assert_relative_eq!(product, 2.0, epsilon = 1e-5);
+        }
+
+        let l = Lemniscate::bernoulli((0.0, 0.0).into(), 1.0, 0.25);
+        let res = l.start();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+    }
+}
@@ -1,15 +1,98 @@
 //! A crate for working with parametric functions
 
+pub mod angle;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+pub mod arclength;
 pub mod bezier;
+pub mod biarc;
+pub mod bspline;
 pub mod circle;
+pub mod clip;
+pub mod continuity;
+pub mod control;
 pub mod core;
+pub mod curve3d;
+pub mod displace;
+pub mod easing;
+pub mod ellipse;
+pub mod error;
+pub mod ext;
+pub mod fillet;
+pub mod function1d;
+mod geometry;
+pub mod label;
+pub mod lemniscate;
+pub mod lissajous;
+pub mod offset;
+pub mod ops;
+pub mod path;
+pub mod polar;
+pub mod polyline;
+pub mod prelude;
+pub mod pyramid;
+pub mod random;
+pub mod rectangle;
+pub mod roulette;
+pub mod route;
 pub mod segment;
+pub mod spiral;
+pub mod spline;
+pub mod stroke;
+pub mod superellipse;
+pub mod warp;
+pub mod wave;
 
+pub use crate::angle::Angle;
+pub use crate::arclength::{ArcLengthTable, ConstantSpeed, Spacing};
 pub use crate::bezier::{
-    BezierFourth, BezierFourthSpline, BezierSecond, BezierSecondSpline, BezierThird,
+    BezierFourth, BezierFourthSpline, BezierNth, BezierSecond, BezierSecondSpline, BezierThird,
     BezierThirdSpline,
 };
+pub use crate::biarc::ArcOrLine;
+pub use crate::bspline::BSpline;
 pub use crate::circle::Circle;
 pub use crate::circle::CircleArc;
-pub use crate::core::{Concat, Point, Repeat, Rotate, RotateTranslate, Scale, Translate, T};
+pub use crate::clip::Rect;
+pub use crate::continuity::{Continuity, ContinuityBreak};
+pub use crate::control::ControlPoints;
+pub use crate::core::{
+    boxed, compile, Affine, Axis, Blend, Component, Concat, DynCurve, EaseParam, Frame2D,
+    IntoParametric2D, Map, Morph, MorphBy, PingPong, Point, Point3, ProjectOntoLine, Repeat,
+    Reverse, Rotate, RotateTranslate, Scale, Shear, Shift, SubCurve, TRange, Transform, Translate,
+    Vector, Vector3, WeightedConcat, Zip, T,
+};
+pub use crate::curve3d::{Circle3, Helix, Segment3};
+#[cfg(feature = "noise")]
+pub use crate::displace::NoiseDisplace;
+pub use crate::displace::{Jitter, ModulateNormal};
+pub use crate::easing::{CubicBezierEasing, Easing, EasingCurve, EasingDirection};
+pub use crate::ellipse::EllipseArc;
+pub use crate::error::ParametricsError;
+pub use crate::ext::ParametricExt;
+pub use crate::fillet::fillet_join;
+#[cfg(feature = "noise")]
+pub use crate::function1d::Noise1D;
+pub use crate::function1d::{
+    Compose1D, Concat1D, Constant, Envelope, Linear, Offset1D, Polynomial, Repeat1D, Reverse1D,
+    Scale1D, Sine1D, Step1D, StepKind, Wave1D,
+};
+pub use crate::label::{place_label, text_on_path, GlyphPlacement, LabelPlacement, Overflow};
+pub use crate::lemniscate::Lemniscate;
+pub use crate::lissajous::Lissajous;
+pub use crate::offset::Offset;
+pub use crate::ops::Curve;
+pub use crate::path::PathBuilder;
+pub use crate::polar::{PolarCurve, Rose};
+pub use crate::polyline::{Polygon, Polyline, RegularPolygon};
+pub use crate::pyramid::{CurvePyramid, PyramidLevel};
+pub use crate::rectangle::{Rectangle, RoundedRect};
+pub use crate::roulette::{Cycloid, Epitrochoid, Hypotrochoid, Involute};
+pub use crate::route::route_around;
 pub use crate::segment::Segment;
+pub use crate::spiral::{Clothoid, Spiral, SpiralKind};
+pub use crate::spline::{Akima, Akima1D, CatmullRom, Hermite};
+pub use crate::stroke::{stroke_outline, LineCap, LineJoin};
+pub use crate::superellipse::{Superellipse, Superformula};
+pub use crate::warp::WarpAlong;
+pub use crate::wave::{SineWave, ZigZag, ZigZagKind};
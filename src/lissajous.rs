@@ -0,0 +1,80 @@
+//! Lissajous figures
+
+use crate::core::{ParametricFunction2D, Point, T};
+
+/// A Lissajous figure around `centre` - `x` oscillates `a` times with amplitude `amplitude_x` and phase `delta` (in
+/// "turns") while `y` oscillates `b` times with amplitude `amplitude_y`. Whole numbers of oscillations close it up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lissajous {
+    pub centre: Point,
+    pub amplitude_x: f32,
+    pub amplitude_y: f32,
+    pub a: f32,
+    pub b: f32,
+    pub delta: f32,
+}
+
+impl Lissajous {
+    pub fn new(
+        centre: Point,
+        amplitude_x: f32,
+        amplitude_y: f32,
+        a: f32,
+        b: f32,
+        delta: f32,
+    ) -> Self {
+        Self {
+            centre,
+            amplitude_x,
+            amplitude_y,
+            a,
+            b,
+            delta,
+        }
+    }
+}
+
+impl ParametricFunction2D for Lissajous {
+    fn evaluate(&self, t: T) -> Point {
+        let theta = t.value() * std::f32::consts::TAU;
+        (
+            self.centre.x
+                + self.amplitude_x * (self.a * theta + self.delta * std::f32::consts::TAU).sin(),
+            self.centre.y + self.amplitude_y * (self.b * theta).sin(),
+        )
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_lissajous() {
+        // a quarter turn out of phase at the same frequency is an ellipse
+        let l = Lissajous::new((1.0, 1.0).into(), 2.0, 1.0, 1.0, 1.0, 0.25);
+
+        let res = l.start();
+        assert_relative_eq!(res.x, 3.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = l.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 2.0, epsilon = 1e-6);
+
+        let res = l.end();
+        assert_relative_eq!(res.x, 3.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        // a figure of eight crosses itself at the centre
+        let l = Lissajous::new((0.0, 0.0).into(), 1.0, 1.0, 1.0, 2.0, 0.0);
+        let res = l.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+        let res = l.evaluate(T::new(0.125));
+        assert_relative_eq!(res.x, f32::sqrt(0.5), epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+    }
+}
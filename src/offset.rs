@@ -0,0 +1,84 @@
+//! Offset (parallel) curves
+
+use std::rc::Rc;
+
+use crate::{
+    bezier::BezierThirdSpline,
+    core::{IntoParametric2D, ParametricFunction2D, Point, T},
+};
+
+/// A thing that implements [`ParametricFunction2D`] displaced by `distance` along its
+/// [`ParametricFunction2D::normal`] - positive distances are to the left of the direction of travel and negative ones
+/// to the right
+#[derive(Clone)]
+pub struct Offset {
+    pub function: Rc<Box<dyn ParametricFunction2D>>,
+    pub distance: f32,
+}
+
+impl Offset {
+    pub fn new(function: impl IntoParametric2D, distance: f32) -> Self {
+        Self {
+            function: function.into_parametric(),
+            distance,
+        }
+    }
+
+    /// approximates the offset curve with cubic Beziers, see [`ParametricFunction2D::approximate_with_cubics`]
+    pub fn to_beziers(&self, tolerance: f32) -> BezierThirdSpline {
+        self.approximate_with_cubics(tolerance)
+    }
+}
+
+impl ParametricFunction2D for Offset {
+    fn evaluate(&self, t: T) -> Point {
+        self.function.evaluate(t) + self.function.normal(t) * self.distance
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        Some(Rc::new(Box::new(Offset {
+            function: self.function.optimize()?,
+            distance: self.distance,
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!("Offset({}, {})", self.function.describe(), self.distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{circle::Circle, segment::Segment};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_offset() {
+        let s = Segment::new((0.0, 0.0).into(), (2.0, 0.0).into());
+        let o = Offset::new(s, 0.5);
+
+        let res = o.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.5, epsilon = 1e-5);
+
+        // an anticlockwise circle's right is outwards
+        let c = Circle::new((0.0, 0.0).into(), 2.0, None);
+        let o = Offset::new(c, -1.0);
+        for p in o.linspace(10) {
+            assert_relative_eq!(p.to_vector().length(), 3.0, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_offset_to_beziers() {
+        let c = Circle::new((0.0, 0.0).into(), 2.0, None);
+        let o = Offset::new(c, 1.0);
+        let beziers = o.to_beziers(1e-3);
+
+        assert_eq!((beziers.points.len() - 1) % 3, 0);
+        for p in beziers.linspace(100) {
+            assert_relative_eq!(p.to_vector().length(), 1.0, epsilon = 2e-3);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Operators for putting curves together
+
+use std::{
+    ops::{Add, Mul, Shr},
+    rc::Rc,
+};
+
+use crate::core::{
+    Affine, Concat, IntoParametric2D, ParametricFunction2D, Point, Repeat, Transform, T,
+};
+
+/// An owned curve which can be put together with operators - `a + b` is the [`Concat`] of `a` followed by `b`,
+/// `a * n` is `a` [`Repeat`]ed `n` times and `a >> transform` is `a` transformed by an [`Affine`]. Adding onto a
+/// [`Curve`] extends it, so `a + b + c` gives each a third of `t`, while `a + (b + c)` gives `a` half. As a [`Repeat`]
+/// always goes through at least once, `a * 0` is the same as `a * 1` - use [`Repeat::try_new`] to rule it out.
+///
+/// ```
+/// use parametrics::{core::ParametricFunction2D, ext::ParametricExt, Circle, Segment, Transform};
+///
+/// let circle = Circle::new((0.0, 0.0).into(), 1.0, None);
+/// let segment = Segment::new((1.0, 0.0).into(), (2.0, 0.0).into());
+/// let curve = (circle.curve() + segment.curve() * 3) >> Transform::scale(2.0, 2.0);
+/// let points = curve.linspace(50);
+/// ```
+#[derive(Clone)]
+pub struct Curve {
+    pub pieces: Concat,
+}
+
+impl Curve {
+    pub fn new(function: impl IntoParametric2D) -> Self {
+        Self {
+            pieces: Concat {
+                functions: vec![function.into_parametric()],
+            },
+        }
+    }
+}
+
+impl ParametricFunction2D for Curve {
+    fn evaluate(&self, t: T) -> Point {
+        self.pieces.evaluate(t)
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        self.pieces.optimize()
+    }
+
+    fn describe(&self) -> String {
+        match self.pieces.functions.as_slice() {
+            [function] => function.describe(),
+            _ => self.pieces.describe(),
+        }
+    }
+}
+
+impl<F: IntoParametric2D> Add<F> for Curve {
+    type Output = Curve;
+
+    fn add(mut self, other: F) -> Curve {
+        self.pieces.push(other);
+        self
+    }
+}
+
+impl Mul<usize> for Curve {
+    type Output = Curve;
+
+    fn mul(self, n: usize) -> Curve {
+        Curve::new(Repeat::new(self, n))
+    }
+}
+
+impl Shr<Transform> for Curve {
+    type Output = Curve;
+
+    fn shr(self, transform: Transform) -> Curve {
+        Curve::new(Affine::new(self, transform))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{compile, Rotate},
+        segment::Segment,
+    };
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_operators() {
+        let a = || Curve::new(Segment::new((0.0, 0.0).into(), (1.0, 0.0).into()));
+        let b = || Segment::new((1.0, 0.0).into(), (1.0, 1.0).into());
+
+        let curve = a() + b() + b();
+        assert_eq!(curve.pieces.functions.len(), 3);
+        let res = curve.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.5);
+
+        let curve = a() + Curve::new(b()) * 2;
+        assert_eq!(curve.describe(), "Concat[Segment, Repeat(Segment, 2)]");
+        let res = curve.evaluate(T::new(0.625));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.5);
+
+        let curve = a() >> Transform::translation(0.0, 2.0);
+        assert_eq!(curve.describe(), "Affine(Segment)");
+        let res = curve.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.5);
+        assert_relative_eq!(res.y, 2.0);
+
+        // still one copy - spelt out, as `* 0` looks like a mistake to clippy
+        let curve = Mul::mul(a(), 0);
+        assert_eq!(curve.describe(), "Repeat(Segment, 0)");
+        let res = curve.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.5);
+
+        // compiling goes through the pieces
+        let curve = Curve::new(Rotate::new(a(), Point::origin(), T::new(0.25))) + b();
+        let expected = curve.linspace(10);
+        let compiled = compile(curve);
+        assert_eq!(compiled.describe(), "Concat[Affine(Segment), Segment]");
+        for (a, b) in compiled.linspace(10).into_iter().zip(expected) {
+            assert_relative_eq!(a.x, b.x, epsilon = 1e-5);
+            assert_relative_eq!(a.y, b.y, epsilon = 1e-5);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Building paths out of lines, Beziers and arcs
+
+use std::rc::Rc;
+
+use crate::{
+    angle::Angle,
+    bezier::{BezierSecond, BezierThird},
+    circle::CircleArc,
+    core::{Concat, IntoParametric2D, ParametricFunction2D, Point},
+    segment::Segment,
+};
+
+/// Fluent builder for paths - each call adds a piece starting at the end of the previous one, and
+/// [`PathBuilder::build`] joins the pieces up into a [`Concat`]
+///
+/// ```
+/// use parametrics::{core::ParametricFunction2D, path::PathBuilder};
+///
+/// let path = PathBuilder::new()
+///     .move_to((0.0, 0.0).into())
+///     .line_to((1.0, 0.0).into())
+///     .quad_to((2.0, 0.0).into(), (2.0, 1.0).into())
+///     .close()
+///     .build();
+/// let points = path.linspace(30);
+/// ```
+pub struct PathBuilder {
+    start: Point,
+    current: Point,
+    functions: Vec<Rc<Box<dyn ParametricFunction2D>>>,
+}
+
+impl PathBuilder {
+    /// a new builder, positioned at the origin
+    pub fn new() -> Self {
+        Self {
+            start: Point::origin(),
+            current: Point::origin(),
+            functions: vec![],
+        }
+    }
+
+    fn push(mut self, function: impl IntoParametric2D, end: Point) -> Self {
+        self.functions.push(function.into_parametric());
+        self.current = end;
+        self
+    }
+
+    /// moves to `to` without adding anything - if pieces have already been added this leaves a jump in the path
+    pub fn move_to(mut self, to: Point) -> Self {
+        self.start = to;
+        self.current = to;
+        self
+    }
+
+    /// adds a [`Segment`] to `to`
+    pub fn line_to(self, to: Point) -> Self {
+        let segment = Segment::new(self.current, to);
+        self.push(segment, to)
+    }
+
+    /// adds a [`BezierSecond`] to `to`
+    pub fn quad_to(self, control: Point, to: Point) -> Self {
+        let bezier = BezierSecond::new(self.current, to, control);
+        self.push(bezier, to)
+    }
+
+    /// adds a [`BezierThird`] to `to`
+    pub fn cubic_to(self, control1: Point, control2: Point, to: Point) -> Self {
+        let bezier = BezierThird::new(self.current, to, control1, control2);
+        self.push(bezier, to)
+    }
+
+    /// adds a circular arc around `centre` sweeping through `sweep` "turns" - positive is anticlockwise, negative
+    /// is clockwise and anything beyond a full turn is clamped
+    pub fn arc_to(self, centre: Point, sweep: f32) -> Self {
+        let offset = self.current - centre;
+        let radius = offset.length();
+        let start_angle = offset.y.atan2(offset.x) / std::f32::consts::TAU;
+        let sweep = sweep.clamp(-1.0, 1.0);
+
+        let arc = CircleArc::with_angles(
+            centre,
+            radius,
+            Some(Angle::turns(start_angle)),
+            Some(Angle::turns(start_angle + sweep)),
+        );
+        let end = arc.end();
+        self.push(arc, end)
+    }
+
+    /// adds a [`Segment`] back to the point of the last [`PathBuilder::move_to`], if not already there
+    pub fn close(self) -> Self {
+        if self.current == self.start {
+            return self;
+        }
+        let start = self.start;
+        self.line_to(start)
+    }
+
+    /// the [`Concat`] of everything added - if nothing was added this is a single degenerate [`Segment`] at the
+    /// current point
+    pub fn build(self) -> Concat {
+        let functions = if self.functions.is_empty() {
+            vec![Segment::new(self.current, self.current).into_parametric()]
+        } else {
+            self.functions
+        };
+        Concat { functions }
+    }
+}
+
+/// Declarative shorthand for the [`PathBuilder`] - each `;` separated command is a builder method name followed by
+/// its comma separated arguments, which are converted with [`Into`] so points can be written as tuples
+///
+/// ```
+/// use parametrics::{core::ParametricFunction2D, path};
+///
+/// let path = path! {
+///     move_to (0.0, 0.0);
+///     line_to (1.0, 0.0);
+///     cubic_to (2.0, 0.0), (2.0, 1.0), (1.0, 1.0);
+///     arc_to (1.0, 0.5), 0.5;
+///     close;
+/// };
+/// let points = path.linspace(40);
+/// ```
+#[macro_export]
+macro_rules! path {
+    ($($command:ident $($arg:expr),*);* $(;)?) => {
+        $crate::path::PathBuilder::new()
+            $(.$command($(::core::convert::Into::into($arg)),*))*
+            .build()
+    };
+}
+
+impl Default for PathBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::T;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_path_builder() {
+        let path = PathBuilder::new()
+            .move_to((0.0, 0.0).into())
+            .line_to((1.0, 0.0).into())
+            .quad_to((2.0, 0.0).into(), (2.0, 1.0).into())
+            .cubic_to((2.0, 2.0).into(), (1.0, 2.0).into(), (0.0, 2.0).into())
+            .close()
+            .build();
+
+        assert_eq!(path.functions.len(), 4);
+
+        let res = path.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = path.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = path.evaluate(T::new(0.75));
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 2.0);
+
+        let res = path.end();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+    }
+
+    #[test]
+    fn test_path_builder_arc() {
+        let path = PathBuilder::new()
+            .move_to((1.0, 0.0).into())
+            .arc_to((0.0, 0.0).into(), 0.25)
+            .arc_to((0.0, 2.0).into(), -0.5)
+            .line_to((0.0, 4.0).into())
+            .build();
+
+        let res = path.evaluate(T::new(1.0 / 3.0));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+
+        // the clockwise arc passes through (-1, 2) on its way to (0, 3)
+        let res = path.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, -1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 2.0, epsilon = 1e-5);
+
+        let res = path.evaluate(T::new(2.0 / 3.0 + 1e-6));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(res.y, 3.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_path_macro() {
+        let path = crate::path! {
+            move_to (0.0, 0.0);
+            line_to (1.0, 0.0);
+            quad_to (2.0, 0.0), (2.0, 1.0);
+            cubic_to (2.0, 2.0), (1.0, 2.0), (0.0, 2.0);
+            close;
+        };
+
+        assert_eq!(path.functions.len(), 4);
+
+        let res = path.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let path = crate::path! {
+            move_to (1.0, 0.0);
+            arc_to (0.0, 0.0), 0.5
+        };
+
+        let res = path.end();
+        assert_relative_eq!(res.x, -1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_path_builder_empty() {
+        let path = PathBuilder::new().move_to((1.0, 1.0).into()).build();
+
+        let res = path.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.0);
+    }
+}
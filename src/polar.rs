@@ -0,0 +1,137 @@
+//! Curves given in polar form
+
+use crate::{
+    core::{ParametricFunction1D, ParametricFunction2D, Point, T},
+    geometry::as_fraction,
+};
+
+/// A curve given by its radius `r(t)` around `centre` as the angle sweeps from `start_angle` to `end_angle` - the
+/// angles are in "turns" like everywhere else, but aren't limited to a single turn so that e.g. spirals can wind
+/// round several times
+pub struct PolarCurve {
+    pub r: Box<dyn ParametricFunction1D>,
+    pub centre: Point,
+    pub start_angle: f32,
+    pub end_angle: f32,
+}
+
+impl PolarCurve {
+    pub fn new(
+        r: impl ParametricFunction1D + 'static,
+        centre: Point,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> Self {
+        Self {
+            r: Box::new(r),
+            centre,
+            start_angle,
+            end_angle,
+        }
+    }
+}
+
+impl ParametricFunction2D for PolarCurve {
+    fn evaluate(&self, t: T) -> Point {
+        let r = self.r.evaluate(t);
+        let theta = self.start_angle + (self.end_angle - self.start_angle) * t.value();
+        let (sin, cos) = (theta * std::f32::consts::TAU).sin_cos();
+        (self.centre.x + r * cos, self.centre.y + r * sin).into()
+    }
+}
+
+/// A rose (rhodonea curve) around `centre` with radius `amplitude * cos(k theta)` - it has `k` petals for odd
+/// whole `k` and `2k` for even. `theta` sweeps through as many turns as it takes to close up, see [`Rose::turns`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rose {
+    pub centre: Point,
+    pub amplitude: f32,
+    pub k: f32,
+    /// the number of turns before the curve repeats, worked out from `k` by [`Rose::new`] - for `k = n / d` in lowest
+    /// terms this is `d / 2` if `n` and `d` are both odd and `d` otherwise. When `k` isn't close to a fraction with
+    /// denominator up to 100 the curve never quite closes, and one turn is used.
+    pub turns: f32,
+}
+
+impl Rose {
+    pub fn new(centre: Point, amplitude: f32, k: f32) -> Self {
+        Self {
+            centre,
+            amplitude,
+            k,
+            turns: match as_fraction(k, 100) {
+                Some((n, d)) if n % 2 == 1 && d % 2 == 1 => d as f32 / 2.0,
+                Some((_, d)) => d as f32,
+                None => 1.0,
+            },
+        }
+    }
+}
+
+impl ParametricFunction2D for Rose {
+    fn evaluate(&self, t: T) -> Point {
+        let theta = t.value() * self.turns * std::f32::consts::TAU;
+        let r = self.amplitude * (self.k * theta).cos();
+        let (sin, cos) = theta.sin_cos();
+        (self.centre.x + r * cos, self.centre.y + r * sin).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_polar_curve() {
+        // a cardioid
+        let p = PolarCurve::new(
+            |t: T| 1.0 - f32::cos(t.value() * std::f32::consts::TAU),
+            (1.0, 1.0).into(),
+            0.0,
+            1.0,
+        );
+
+        let res = p.start();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = p.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, -1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        let res = p.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 2.0, epsilon = 1e-6);
+
+        // winding round more than once
+        let spiral = PolarCurve::new(|t: T| t.value(), (0.0, 0.0).into(), 0.25, 2.25);
+        let res = spiral.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.5, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_rose() {
+        // three petals, traced in half a turn
+        let r = Rose::new((1.0, 1.0).into(), 2.0, 3.0);
+        assert_relative_eq!(r.turns, 0.5);
+
+        let res = r.start();
+        assert_relative_eq!(res.x, 3.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = r.end();
+        assert_relative_eq!(res.x, 3.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+
+        // between the petals
+        let res = r.evaluate(T::new(1.0 / 6.0));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+
+        assert_relative_eq!(Rose::new((0.0, 0.0).into(), 1.0, 2.0).turns, 1.0);
+        assert_relative_eq!(Rose::new((0.0, 0.0).into(), 1.0, 0.75).turns, 4.0);
+        assert_relative_eq!(Rose::new((0.0, 0.0).into(), 1.0, 1.0 / 3.0).turns, 1.5);
+    }
+}
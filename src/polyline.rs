@@ -0,0 +1,414 @@
+//! Polylines and polygons through lists of points
+
+use crate::{
+    core::{ParametricFunction2D, Point, Vector, T},
+    error::{check_finite, check_points, check_radius, ParametricsError},
+};
+
+/// Straight lines joining up `points` in order. If `by_length` is set each line takes a share of `t` in proportion
+/// to its length, so the polyline is traversed at a constant speed - otherwise every line takes an equal share. A
+/// single point is a degenerate polyline that stays put, and no points at all give the origin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polyline {
+    pub points: Vec<Point>,
+    pub by_length: bool,
+}
+
+impl Polyline {
+    /// a polyline with each line taking an equal share of `t`
+    pub fn new(points: Vec<Point>) -> Self {
+        Self {
+            points,
+            by_length: false,
+        }
+    }
+
+    /// as [`Polyline::new`] but fails if there are no points or any of them isn't finite
+    pub fn try_new(points: Vec<Point>) -> Result<Self, ParametricsError> {
+        if points.is_empty() {
+            return Err(ParametricsError::TooFewPoints {
+                needed: 1,
+                found: 0,
+            });
+        }
+        check_points(&points)?;
+        Ok(Self::new(points))
+    }
+
+    /// a polyline with each line taking a share of `t` in proportion to its length
+    pub fn by_length(points: Vec<Point>) -> Self {
+        Self {
+            points,
+            by_length: true,
+        }
+    }
+
+    /// returns the total length of the lines
+    pub fn length(&self) -> f32 {
+        self.points.windows(2).map(|w| (w[1] - w[0]).length()).sum()
+    }
+}
+
+impl ParametricFunction2D for Polyline {
+    fn evaluate(&self, t: T) -> Point {
+        along(&self.points, self.by_length, t)
+    }
+}
+
+/// The points are joined up as a [`Polyline`] would be, each line taking an equal share of `t` - so sampled data can
+/// go straight into the combinators
+impl ParametricFunction2D for Vec<Point> {
+    fn evaluate(&self, t: T) -> Point {
+        along(self, false, t)
+    }
+
+    fn describe(&self) -> String {
+        "Vec<Point>".to_string()
+    }
+}
+
+/// As for `Vec<Point>`
+impl ParametricFunction2D for &[Point] {
+    fn evaluate(&self, t: T) -> Point {
+        along(self, false, t)
+    }
+
+    fn describe(&self) -> String {
+        "&[Point]".to_string()
+    }
+}
+
+/// A closed polygon with corners at `points` in order - like a [`Polyline`] that comes back round to the first
+/// point, with `by_length` working in the same way
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    pub points: Vec<Point>,
+    pub by_length: bool,
+}
+
+impl Polygon {
+    /// a polygon with each side taking an equal share of `t`
+    pub fn new(points: Vec<Point>) -> Self {
+        Self {
+            points,
+            by_length: false,
+        }
+    }
+
+    /// as [`Polygon::new`] but fails if there are no points or any of them isn't finite
+    pub fn try_new(points: Vec<Point>) -> Result<Self, ParametricsError> {
+        if points.is_empty() {
+            return Err(ParametricsError::TooFewPoints {
+                needed: 1,
+                found: 0,
+            });
+        }
+        check_points(&points)?;
+        Ok(Self::new(points))
+    }
+
+    /// a polygon with each side taking a share of `t` in proportion to its length
+    pub fn by_length(points: Vec<Point>) -> Self {
+        Self {
+            points,
+            by_length: true,
+        }
+    }
+
+    /// the sides of the polygon as pairs of corners
+    fn sides(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.points
+            .iter()
+            .zip(self.points.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+    }
+
+    /// returns the total length of the sides
+    pub fn perimeter(&self) -> f32 {
+        self.sides().map(|(a, b)| (b - a).length()).sum()
+    }
+
+    /// returns the area enclosed, positive if the corners go round anticlockwise and negative if clockwise
+    pub fn signed_area(&self) -> f32 {
+        self.sides()
+            .map(|(a, b)| a.to_vector().cross(b.to_vector()))
+            .sum::<f32>()
+            / 2.0
+    }
+
+    /// returns the area enclosed
+    pub fn area(&self) -> f32 {
+        self.signed_area().abs()
+    }
+
+    /// returns the centre of mass of the area enclosed - or of the corners, if that area is zero
+    pub fn centroid(&self) -> Point {
+        let area = self.signed_area();
+        if area == 0.0 {
+            let sum: Vector = self.points.iter().map(|p| p.to_vector()).sum();
+            return (sum / self.points.len().max(1) as f32).to_point();
+        }
+
+        let sum: Vector = self
+            .sides()
+            .map(|(a, b)| (a.to_vector() + b.to_vector()) * a.to_vector().cross(b.to_vector()))
+            .sum();
+        (sum / (6.0 * area)).to_point()
+    }
+
+    /// returns whether `point` is inside the polygon, by the even-odd rule
+    pub fn contains(&self, point: Point) -> bool {
+        let mut inside = false;
+        for (a, b) in self.sides() {
+            if (a.y > point.y) != (b.y > point.y) {
+                let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if point.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+}
+
+impl ParametricFunction2D for Polygon {
+    fn evaluate(&self, t: T) -> Point {
+        around(&self.points, self.by_length, t)
+    }
+}
+
+/// A regular polygon with `sides` sides and its corners on the circle of `radius` around `centre` - the first corner
+/// is on the positive x-axis turned anticlockwise by `rotation` (in "turns"), and the rest follow anticlockwise
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegularPolygon {
+    pub centre: Point,
+    pub radius: f32,
+    pub sides: usize,
+    pub rotation: T,
+}
+
+impl RegularPolygon {
+    pub fn new(centre: Point, radius: f32, sides: usize, rotation: T) -> Self {
+        Self {
+            centre,
+            radius,
+            sides,
+            rotation,
+        }
+    }
+
+    /// as [`RegularPolygon::new`] but fails if anything isn't finite, the radius isn't above zero or there are
+    /// fewer than three sides
+    pub fn try_new(
+        centre: Point,
+        radius: f32,
+        sides: usize,
+        rotation: T,
+    ) -> Result<Self, ParametricsError> {
+        check_points(&[centre])?;
+        check_radius(radius)?;
+        check_finite([rotation.value()])?;
+        if sides < 3 {
+            return Err(ParametricsError::TooFewPoints {
+                needed: 3,
+                found: sides,
+            });
+        }
+        Ok(Self::new(centre, radius, sides, rotation))
+    }
+
+    /// returns corner `k`, counting anticlockwise from the first
+    pub fn corner(&self, k: usize) -> Point {
+        let angle =
+            (k as f32 / self.sides.max(1) as f32 + self.rotation.value()) * std::f32::consts::TAU;
+        let (sin, cos) = angle.sin_cos();
+        (
+            self.centre.x + self.radius * cos,
+            self.centre.y + self.radius * sin,
+        )
+            .into()
+    }
+
+    /// returns the corners, anticlockwise from the first
+    pub fn corners(&self) -> Vec<Point> {
+        (0..self.sides).map(|k| self.corner(k)).collect()
+    }
+
+    /// returns the same shape as a [`Polygon`], for its area, containment and so on
+    pub fn to_polygon(&self) -> Polygon {
+        Polygon::new(self.corners())
+    }
+}
+
+impl ParametricFunction2D for RegularPolygon {
+    fn evaluate(&self, t: T) -> Point {
+        if self.sides == 0 {
+            return self.centre;
+        }
+        let position = t.value() * self.sides as f32;
+        let index = (position.floor() as usize).min(self.sides - 1);
+        self.corner(index)
+            .lerp(self.corner(index + 1), position - index as f32)
+    }
+}
+
+/// evaluates the polyline through `points` at `t`, see [`Polyline`]
+pub(crate) fn along(points: &[Point], by_length: bool, t: T) -> Point {
+    walk(points, false, by_length, t)
+}
+
+/// evaluates the closed loop through `points` and back to the first at `t`, see [`Polygon`]
+pub(crate) fn around(points: &[Point], by_length: bool, t: T) -> Point {
+    walk(points, true, by_length, t)
+}
+
+/// evaluates the lines through `points` at `t`, going on from the last point back to the first if `closed`
+fn walk(points: &[Point], closed: bool, by_length: bool, t: T) -> Point {
+    let lines = (points.len() + closed as usize).saturating_sub(1);
+    if lines == 0 || points.len() == 1 {
+        return points.first().copied().unwrap_or_else(Point::origin);
+    }
+
+    let point = |i: usize| points[i % points.len()];
+    let length = |i: usize| (point(i + 1) - point(i)).length();
+    let total: f32 = (0..lines).map(length).sum();
+    if !by_length || total <= 0.0 {
+        let position = t.value() * lines as f32;
+        let index = (position.floor() as usize).min(lines - 1);
+        return point(index).lerp(point(index + 1), position - index as f32);
+    }
+
+    let mut remaining = t.value() * total;
+    for i in 0..lines {
+        let length = length(i);
+        if remaining <= length && length > 0.0 {
+            return point(i).lerp(point(i + 1), remaining / length);
+        }
+        remaining -= length;
+    }
+    point(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_points() {
+        let points: Vec<Point> = vec![(0.0, 0.0).into(), (1.0, 0.0).into(), (1.0, 3.0).into()];
+
+        let res = points.evaluate(T::new(0.75));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.5);
+
+        let slice = &points[1..];
+        let res = slice.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.5);
+
+        let reversed = crate::core::Reverse::new(points);
+        let res = reversed.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.5);
+    }
+
+    #[test]
+    fn test_polyline() {
+        let points: Vec<Point> = vec![(0.0, 0.0).into(), (1.0, 0.0).into(), (1.0, 3.0).into()];
+
+        let p = Polyline::new(points.clone());
+        let res = p.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = p.evaluate(T::new(0.75));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.5);
+
+        let p = Polyline::by_length(points);
+        assert_relative_eq!(p.length(), 4.0);
+
+        let res = p.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = p.end();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 3.0);
+
+        let p = Polyline::new(vec![(2.0, 2.0).into()]);
+        let res = p.evaluate(T::new(0.3));
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 2.0);
+    }
+
+    #[test]
+    fn test_polygon() {
+        let square = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (2.0, 0.0).into(),
+            (2.0, 2.0).into(),
+            (0.0, 2.0).into(),
+        ]);
+
+        let res = square.evaluate(T::new(0.375));
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = square.end();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        assert_relative_eq!(square.perimeter(), 8.0);
+        assert_relative_eq!(square.signed_area(), 4.0);
+        assert!(square.contains((1.0, 1.5).into()));
+        assert!(!square.contains((3.0, 1.0).into()));
+
+        // clockwise this time
+        let triangle = Polygon::by_length(vec![
+            (0.0, 0.0).into(),
+            (0.0, 3.0).into(),
+            (3.0, 0.0).into(),
+        ]);
+        assert_relative_eq!(triangle.signed_area(), -4.5);
+        assert_relative_eq!(triangle.area(), 4.5);
+
+        let res = triangle.centroid();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = triangle.evaluate(T::new(3.0 / triangle.perimeter()));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 3.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_regular_polygon() {
+        let hexagon = RegularPolygon::new((1.0, 1.0).into(), 2.0, 6, T::start());
+
+        let res = hexagon.start();
+        assert_relative_eq!(res.x, 3.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = hexagon.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, -1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        let res = hexagon.end();
+        assert_relative_eq!(res.x, 3.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        // halfway along the first side
+        let res = hexagon.evaluate(T::new(1.0 / 12.0));
+        assert_relative_eq!(res.x, 1.0 + 1.5, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0 + f32::sqrt(3.0) / 2.0, epsilon = 1e-6);
+
+        // a square standing on its edge
+        let square = RegularPolygon::new((0.0, 0.0).into(), f32::sqrt(2.0), 4, T::new(0.125));
+        let res = square.start();
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(square.to_polygon().area(), 4.0, epsilon = 1e-5);
+    }
+}
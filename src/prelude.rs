@@ -0,0 +1,49 @@
+//! Commonly used traits, combinators and curves
+//!
+//! ```
+//! use parametrics::prelude::*;
+//!
+//! let c = Circle::new((0.0, 0.0).into(), 1.0, None);
+//! let r = Rotate::new(c, (1.0, 0.0).into(), Angle::degrees(90.0));
+//! let points = r.linspace(10);
+//! ```
+
+pub use crate::angle::Angle;
+pub use crate::arclength::{ConstantSpeed, Spacing};
+pub use crate::bezier::{
+    BezierFourth, BezierFourthSpline, BezierNth, BezierSecond, BezierSecondSpline, BezierThird,
+    BezierThirdSpline,
+};
+pub use crate::bspline::BSpline;
+pub use crate::circle::{Circle, CircleArc};
+pub use crate::control::ControlPoints;
+pub use crate::core::{
+    boxed, compile, Affine, Axis, Blend, Component, Concat, DynCurve, EaseParam, Frame2D,
+    IntoParametric2D, Map, Morph, MorphBy, ParametricFunction1D, ParametricFunction2D,
+    ParametricFunction3D, PingPong, Point, Point3, ProjectOntoLine, Repeat, Reverse, Rotate,
+    RotateTranslate, Scale, Shear, Shift, SubCurve, TRange, Transform, Translate, Vector, Vector3,
+    WeightedConcat, Zip, T,
+};
+pub use crate::curve3d::{Circle3, Helix, Segment3};
+pub use crate::easing::{CubicBezierEasing, Easing, EasingCurve, EasingDirection};
+pub use crate::ellipse::EllipseArc;
+pub use crate::ext::ParametricExt;
+pub use crate::function1d::{
+    Compose1D, Concat1D, Constant, Envelope, Linear, Offset1D, Polynomial, Repeat1D, Reverse1D,
+    Scale1D, Sine1D, Step1D, StepKind, Wave1D,
+};
+pub use crate::lemniscate::Lemniscate;
+pub use crate::lissajous::Lissajous;
+pub use crate::offset::Offset;
+pub use crate::ops::Curve;
+pub use crate::path::PathBuilder;
+pub use crate::polar::{PolarCurve, Rose};
+pub use crate::polyline::{Polygon, Polyline, RegularPolygon};
+pub use crate::rectangle::{Rectangle, RoundedRect};
+pub use crate::roulette::{Cycloid, Epitrochoid, Hypotrochoid, Involute};
+pub use crate::segment::Segment;
+pub use crate::spiral::{Clothoid, Spiral};
+pub use crate::spline::{Akima, Akima1D, CatmullRom, Hermite};
+pub use crate::superellipse::{Superellipse, Superformula};
+pub use crate::warp::WarpAlong;
+pub use crate::wave::{SineWave, ZigZag};
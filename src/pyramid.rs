@@ -0,0 +1,140 @@
+//! Multi-resolution approximations of curves
+
+use crate::{
+    core::{ParametricFunction2D, Point},
+    geometry::closest_on_segment,
+};
+
+/// A single level of a [`CurvePyramid`] - no point of the original samples is further than `tolerance` from
+/// the polyline through `points`
+pub struct PyramidLevel {
+    pub tolerance: f32,
+    pub points: Vec<Point>,
+}
+
+/// Progressively simplified polyline versions of the same curve, ordered from finest to coarsest
+pub struct CurvePyramid {
+    pub levels: Vec<PyramidLevel>,
+}
+
+impl CurvePyramid {
+    /// samples `function` at `n` equally spaced points (the finest level, with a tolerance of `0`) and builds a
+    /// further level for each of the `tolerances` by Ramer-Douglas-Peucker simplification of those samples
+    pub fn new(function: &dyn ParametricFunction2D, n: usize, tolerances: &[f32]) -> Self {
+        let samples = function.linspace(n);
+
+        let mut tolerances: Vec<f32> = tolerances.iter().copied().filter(|&t| t > 0.0).collect();
+        tolerances.sort_by(f32::total_cmp);
+        tolerances.dedup();
+
+        let mut levels = vec![PyramidLevel {
+            tolerance: 0.0,
+            points: samples.clone(),
+        }];
+        levels.extend(tolerances.into_iter().map(|tolerance| PyramidLevel {
+            tolerance,
+            points: simplify(&samples, tolerance),
+        }));
+
+        Self { levels }
+    }
+
+    /// returns the coarsest level whose tolerance does not exceed `tolerance`
+    pub fn select(&self, tolerance: f32) -> &PyramidLevel {
+        let index = self
+            .levels
+            .partition_point(|level| level.tolerance <= tolerance);
+        &self.levels[index.saturating_sub(1)]
+    }
+}
+
+/// Ramer-Douglas-Peucker simplification of a polyline
+fn simplify(points: &[Point], tolerance: f32) -> Vec<Point> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let (index, distance) = (first + 1..last)
+            .map(|i| {
+                (
+                    i,
+                    distance_to_segment(points[i], points[first], points[last]),
+                )
+            })
+            .fold((first, 0.0), |best, d| if d.1 > best.1 { d } else { best });
+
+        if distance > tolerance {
+            keep[index] = true;
+            stack.push((first, index));
+            stack.push((index, last));
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter(|&(_, k)| k)
+        .map(|(&p, _)| p)
+        .collect()
+}
+
+fn distance_to_segment(p: Point, a: Point, b: Point) -> f32 {
+    (p - closest_on_segment(p, a, b)).length()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{circle::Circle, segment::Segment};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_pyramid_levels() {
+        let c = Circle::new((0.0, 0.0).into(), 1.0, None);
+        let pyramid = CurvePyramid::new(&c, 256, &[0.1, 0.01, 0.001]);
+
+        assert_eq!(pyramid.levels.len(), 4);
+        assert_eq!(pyramid.levels[0].points.len(), 257);
+
+        for pair in pyramid.levels.windows(2) {
+            assert!(pair[0].tolerance < pair[1].tolerance);
+            assert!(pair[0].points.len() >= pair[1].points.len());
+        }
+
+        for level in &pyramid.levels {
+            for p in &pyramid.levels[0].points {
+                let d = level
+                    .points
+                    .windows(2)
+                    .map(|s| distance_to_segment(*p, s[0], s[1]))
+                    .fold(f32::INFINITY, f32::min);
+                assert!(d <= level.tolerance + 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pyramid_select() {
+        let c = Circle::new((0.0, 0.0).into(), 1.0, None);
+        let pyramid = CurvePyramid::new(&c, 256, &[0.1, 0.01]);
+
+        assert_relative_eq!(pyramid.select(0.0).tolerance, 0.0);
+        assert_relative_eq!(pyramid.select(0.005).tolerance, 0.0);
+        assert_relative_eq!(pyramid.select(0.05).tolerance, 0.01);
+        assert_relative_eq!(pyramid.select(1.0).tolerance, 0.1);
+    }
+
+    #[test]
+    fn test_pyramid_straight_line() {
+        let s = Segment::new((0.0, 0.0).into(), (1.0, 1.0).into());
+        let pyramid = CurvePyramid::new(&s, 100, &[0.001]);
+
+        assert_eq!(pyramid.select(0.001).points.len(), 2);
+    }
+}
@@ -0,0 +1,129 @@
+//! Randomness - the default source of it used by [`ParametricFunction2D::random_point`] and friends, which can be
+//! seeded for reproducible output, and random curves for trying things out
+//!
+//! [`ParametricFunction2D::random_point`]: crate::core::ParametricFunction2D::random_point
+
+use std::cell::RefCell;
+
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+
+use crate::{
+    bezier::{BezierThird, BezierThirdSpline},
+    clip::Rect,
+    core::{Point, Vector},
+    polyline::Polyline,
+};
+
+thread_local! {
+    static DEFAULT_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// seeds the default source of randomness on this thread, so the random functions that don't take an rng give the
+/// same results each run
+pub fn seed(seed: u64) {
+    DEFAULT_RNG.with(|rng| *rng.borrow_mut() = Some(StdRng::seed_from_u64(seed)));
+}
+
+/// goes back to unseeded randomness on this thread, from [`rand::thread_rng`]
+pub fn unseed() {
+    DEFAULT_RNG.with(|rng| *rng.borrow_mut() = None);
+}
+
+/// calls `f` with the default source of randomness - the seeded one if there is one, see [`seed`]
+pub(crate) fn with_default_rng<R>(f: impl FnOnce(&mut dyn RngCore) -> R) -> R {
+    DEFAULT_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+        Some(rng) => f(rng),
+        None => f(&mut rand::thread_rng()),
+    })
+}
+
+/// returns a point drawn uniformly from inside `rect`
+fn point_in(rect: Rect, rng: &mut dyn RngCore) -> Point {
+    let (x, y): (f32, f32) = rng.gen();
+    rect.min + (rect.max - rect.min).component_mul(Vector::new(x, y))
+}
+
+impl BezierThird {
+    /// a cubic Bezier with its ends and controls all drawn uniformly from inside `rect`
+    pub fn random_in(rect: Rect, rng: &mut dyn RngCore) -> Self {
+        let [start, end, control1, control2] = [(); 4].map(|_| point_in(rect, rng));
+        Self::new(start, end, control1, control2)
+    }
+}
+
+impl BezierThirdSpline {
+    /// a smooth spline through a random walk of `n` steps of length `step` in random directions from `start`
+    pub fn random_walk(start: Point, n: usize, step: f32, rng: &mut dyn RngCore) -> Self {
+        let mut points = vec![start];
+        for _ in 0..n {
+            let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+            let last = points[points.len() - 1];
+            points.push(last + Vector::new(angle.cos(), angle.sin()) * step);
+        }
+        Self::smooth_through(&points, 0.0)
+    }
+}
+
+impl Polyline {
+    /// a polyline through `n` points drawn uniformly from inside `rect`
+    pub fn random_in(rect: Rect, n: usize, rng: &mut dyn RngCore) -> Self {
+        Self::new((0..n).map(|_| point_in(rect, rng)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::control::ControlPoints;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_random_curves() {
+        let rect = Rect::new((1.0, 2.0).into(), (3.0, 3.0).into());
+        let mut rng = StdRng::seed_from_u64(5);
+
+        let b = BezierThird::random_in(rect, &mut rng);
+        for p in [b.start, b.end, b.control1, b.control2] {
+            assert!(rect.contains(p));
+        }
+
+        let p = Polyline::random_in(rect, 20, &mut rng);
+        assert_eq!(p.points.len(), 20);
+        assert!(p.points.iter().all(|&p| rect.contains(p)));
+
+        let w = BezierThirdSpline::random_walk((0.0, 0.0).into(), 5, 2.0, &mut rng);
+        assert_eq!(w.segments().count(), 5);
+        let points: Vec<Point> = w.segments().map(|b| b.end).collect();
+        let mut last = Point::origin();
+        for p in points {
+            assert_relative_eq!((p - last).length(), 2.0, epsilon = 1e-5);
+            last = p;
+        }
+        assert!(!w.control_points().is_empty());
+
+        // the same seed gives the same shapes
+        let a = BezierThird::random_in(rect, &mut StdRng::seed_from_u64(9));
+        let b = BezierThird::random_in(rect, &mut StdRng::seed_from_u64(9));
+        assert_eq!(a.control1, b.control1);
+    }
+
+    #[test]
+    fn test_seeded_nested() {
+        use crate::core::{ParametricFunction2D, T};
+        use crate::segment::Segment;
+
+        // a curve which itself draws from the default rng when evaluated
+        let wobbly = |t: T| -> Point {
+            let s = Segment::new((0.0, 0.0).into(), (0.0, 1.0).into());
+            Point::new(t.value(), 0.0) + s.random_point().to_vector()
+        };
+
+        seed(3);
+        let a = wobbly.random_points(5);
+        let b = wobbly.random_point();
+        seed(3);
+        assert_eq!(wobbly.random_points(5), a);
+        assert_eq!(wobbly.random_point(), b);
+        unseed();
+    }
+}
@@ -0,0 +1,235 @@
+//! Rectangles, with square or rounded corners
+
+use euclid::UnknownUnit;
+
+use crate::{
+    angle::Angle,
+    circle::CircleArc,
+    clip::Rect,
+    core::{ParametricFunction2D, Point, T},
+    polyline::Polygon,
+    segment::Segment,
+};
+
+/// The perimeter of an axis aligned rectangle - starting from the minimum corner (bottom left with the y-axis
+/// pointing up) and going round anticlockwise, at a constant speed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rectangle {
+    pub rect: Rect,
+}
+
+impl Rectangle {
+    pub fn new(rect: Rect) -> Self {
+        Self { rect }
+    }
+
+    /// the rectangle with opposite corners `a` and `b`, in any order
+    pub fn from_corners(a: Point, b: Point) -> Self {
+        Self::new(Rect::from_points([a, b]))
+    }
+
+    /// returns the corners, anticlockwise from the minimum one
+    pub fn corners(&self) -> [Point; 4] {
+        let (min, max) = (self.rect.min, self.rect.max);
+        [min, Point::new(max.x, min.y), max, Point::new(min.x, max.y)]
+    }
+
+    /// returns the same shape as a [`Polygon`], for its area, containment and so on
+    pub fn to_polygon(&self) -> Polygon {
+        Polygon::by_length(self.corners().to_vec())
+    }
+}
+
+impl From<Rect> for Rectangle {
+    fn from(rect: Rect) -> Self {
+        Self::new(rect)
+    }
+}
+
+impl From<euclid::Rect<f32, UnknownUnit>> for Rectangle {
+    fn from(rect: euclid::Rect<f32, UnknownUnit>) -> Self {
+        Self::new(rect.to_box2d())
+    }
+}
+
+impl ParametricFunction2D for Rectangle {
+    fn evaluate(&self, t: T) -> Point {
+        let (width, height) = (self.rect.width(), self.rect.height());
+        let perimeter = 2.0 * (width + height);
+        if perimeter <= 0.0 {
+            return self.rect.min;
+        }
+
+        let mut remaining = t.value() * perimeter;
+        let corners = self.corners();
+        for (i, length) in [width, height, width, height].into_iter().enumerate() {
+            if remaining <= length && length > 0.0 {
+                return corners[i].lerp(corners[(i + 1) % 4], remaining / length);
+            }
+            remaining -= length;
+        }
+        self.rect.min
+    }
+}
+
+/// An axis aligned rectangle with its corners rounded off by quarter circles - `radii` are for the corners in the
+/// order of [`Rectangle::corners`], and are capped at half the shorter side. It starts where the bottom side leaves
+/// the rounding of the minimum corner and goes round anticlockwise, at a constant speed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundedRect {
+    pub rect: Rect,
+    pub radii: [f32; 4],
+}
+
+impl RoundedRect {
+    /// a rectangle with every corner rounded by `radius`
+    pub fn new(rect: Rect, radius: f32) -> Self {
+        Self::with_radii(rect, [radius; 4])
+    }
+
+    /// a rectangle with each corner rounded by its own radius
+    pub fn with_radii(rect: Rect, radii: [f32; 4]) -> Self {
+        Self { rect, radii }
+    }
+
+    /// returns the sides and corner arcs in order, each with its length
+    fn pieces(&self) -> [(Piece, f32); 8] {
+        let (min, max) = (self.rect.min, self.rect.max);
+        let cap = self.rect.width().min(self.rect.height()) / 2.0;
+        let [r0, r1, r2, r3] = self.radii.map(|r| r.clamp(0.0, cap));
+
+        let arc = |centre: Point, radius: f32, start: f32| {
+            let arc = CircleArc::with_angles(
+                centre,
+                radius,
+                Some(Angle::turns(start)),
+                Some(Angle::turns(start + 0.25)),
+            );
+            (Piece::Corner(arc), radius * std::f32::consts::FRAC_PI_2)
+        };
+        let side =
+            |from: Point, to: Point| (Piece::Side(Segment::new(from, to)), (to - from).length());
+
+        [
+            side(Point::new(min.x + r0, min.y), Point::new(max.x - r1, min.y)),
+            arc(Point::new(max.x - r1, min.y + r1), r1, 0.75),
+            side(Point::new(max.x, min.y + r1), Point::new(max.x, max.y - r2)),
+            arc(Point::new(max.x - r2, max.y - r2), r2, 0.0),
+            side(Point::new(max.x - r2, max.y), Point::new(min.x + r3, max.y)),
+            arc(Point::new(min.x + r3, max.y - r3), r3, 0.25),
+            side(Point::new(min.x, max.y - r3), Point::new(min.x, min.y + r0)),
+            arc(Point::new(min.x + r0, min.y + r0), r0, 0.5),
+        ]
+    }
+}
+
+/// One of the sides or corners of a [`RoundedRect`]
+enum Piece {
+    Side(Segment),
+    Corner(CircleArc),
+}
+
+impl Piece {
+    fn evaluate(&self, t: T) -> Point {
+        match self {
+            Piece::Side(side) => side.evaluate(t),
+            Piece::Corner(corner) => corner.evaluate(t),
+        }
+    }
+}
+
+impl ParametricFunction2D for RoundedRect {
+    fn evaluate(&self, t: T) -> Point {
+        // each piece takes a share of `t` proportional to its length, as in a `WeightedConcat`
+        let pieces = self.pieces();
+        if t == T::start() {
+            return pieces[0].0.evaluate(t);
+        }
+
+        let total: f32 = pieces.iter().map(|&(_, length)| length).sum();
+        let target = t.value() * total;
+
+        let mut cumulative = 0.0;
+        for (piece, length) in &pieces {
+            if *length <= 0.0 {
+                continue;
+            }
+            if target < cumulative + length {
+                return piece.evaluate(T::new((target - cumulative) / length));
+            }
+            cumulative += length;
+        }
+
+        pieces[7].0.evaluate(T::end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_rectangle() {
+        let r = Rectangle::from_corners((3.0, 2.0).into(), (0.0, 0.0).into());
+
+        let res = r.start();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = r.evaluate(T::new(0.3));
+        assert_relative_eq!(res.x, 3.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+
+        let res = r.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 3.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 2.0, epsilon = 1e-6);
+
+        let res = r.evaluate(T::new(0.9));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        let res = r.end();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+
+        let r: Rectangle = euclid::Rect::new((1.0, 1.0).into(), (3.0, 2.0).into()).into();
+        assert_relative_eq!(r.rect.max.x, 4.0);
+        assert_relative_eq!(r.to_polygon().signed_area(), 6.0);
+    }
+
+    #[test]
+    fn test_rounded_rect() {
+        let rect = Rect::new((0.0, 0.0).into(), (4.0, 2.0).into());
+        let r = RoundedRect::new(rect, 1.0);
+
+        let res = r.start();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = r.end();
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+
+        // the sides are 2 long and the arcs pi / 2, so the first corner is rounded by this point
+        let perimeter = 4.0 + 2.0 * std::f32::consts::PI;
+        let res = r.evaluate(T::new((2.0 + std::f32::consts::FRAC_PI_4) / perimeter));
+        assert_relative_eq!(res.x, 3.0 + f32::sqrt(0.5), epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0 - f32::sqrt(0.5), epsilon = 1e-5);
+
+        // capped at half the shorter side, and square corners are allowed
+        let r = RoundedRect::with_radii(rect, [5.0, 0.0, 0.0, 0.0]);
+        let res = r.start();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.0);
+        assert_relative_eq!(
+            r.arc_length(1e-4),
+            10.0 + std::f32::consts::FRAC_PI_2,
+            epsilon = 1e-3
+        );
+        assert!(r
+            .linspace(100)
+            .iter()
+            .any(|p| (*p - Point::new(4.0, 2.0)).length() < 0.1));
+    }
+}
@@ -0,0 +1,288 @@
+//! Roulettes - the curves traced by a pen fixed to a circle as it rolls along a line or round another circle
+
+use crate::{
+    core::{ParametricFunction2D, Point, T},
+    geometry::as_fraction,
+};
+
+/// The curve traced by a pen `pen` away from the centre of a circle of `radius` as it rolls `arches` times along the
+/// x-axis from `origin` (where the circle touches the line at the start). With the pen on the rim this is the
+/// cycloid - nearer the centre it's curtate, and further out it's prolate and loops back on itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycloid {
+    pub origin: Point,
+    pub radius: f32,
+    pub pen: f32,
+    pub arches: f32,
+}
+
+impl Cycloid {
+    /// the cycloid, with the pen on the rim
+    pub fn new(origin: Point, radius: f32, arches: f32) -> Self {
+        Self::trochoid(origin, radius, radius, arches)
+    }
+
+    /// the curtate or prolate cycloid with the pen `pen` away from the centre
+    pub fn trochoid(origin: Point, radius: f32, pen: f32, arches: f32) -> Self {
+        Self {
+            origin,
+            radius,
+            pen,
+            arches,
+        }
+    }
+}
+
+impl ParametricFunction2D for Cycloid {
+    fn evaluate(&self, t: T) -> Point {
+        let theta = t.value() * self.arches * std::f32::consts::TAU;
+        let (sin, cos) = theta.sin_cos();
+        (
+            self.origin.x + self.radius * theta - self.pen * sin,
+            self.origin.y + self.radius - self.pen * cos,
+        )
+            .into()
+    }
+}
+
+/// The curve traced by a pen `pen` away from the centre of a circle of `rolling_radius` as it rolls round the outside
+/// of a circle of `fixed_radius` around `centre` - a spirograph drawn from outside the ring. It starts on the
+/// positive x-axis and goes round as many times as it takes to close up, see [`Epitrochoid::revolutions`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Epitrochoid {
+    pub centre: Point,
+    pub fixed_radius: f32,
+    pub rolling_radius: f32,
+    pub pen: f32,
+    /// the number of times round the fixed circle before the curve closes up, worked out from the radii by
+    /// [`Epitrochoid::new`] - see [`revolutions`]
+    pub revolutions: f32,
+}
+
+impl Epitrochoid {
+    pub fn new(centre: Point, fixed_radius: f32, rolling_radius: f32, pen: f32) -> Self {
+        Self {
+            centre,
+            fixed_radius,
+            rolling_radius,
+            pen,
+            revolutions: revolutions(fixed_radius, rolling_radius),
+        }
+    }
+
+    /// the epicycloid, with the pen on the rim of the rolling circle
+    pub fn epicycloid(centre: Point, fixed_radius: f32, rolling_radius: f32) -> Self {
+        Self::new(centre, fixed_radius, rolling_radius, rolling_radius)
+    }
+}
+
+impl ParametricFunction2D for Epitrochoid {
+    fn evaluate(&self, t: T) -> Point {
+        let theta = t.value() * self.revolutions * std::f32::consts::TAU;
+        let sum = self.fixed_radius + self.rolling_radius;
+        let (sin, cos) = theta.sin_cos();
+        let (pen_sin, pen_cos) = (sum / self.rolling_radius * theta).sin_cos();
+        (
+            self.centre.x + sum * cos - self.pen * pen_cos,
+            self.centre.y + sum * sin - self.pen * pen_sin,
+        )
+            .into()
+    }
+}
+
+/// The curve traced by a pen `pen` away from the centre of a circle of `rolling_radius` as it rolls round the inside
+/// of a circle of `fixed_radius` around `centre` - the classic spirograph. It starts on the positive x-axis and goes
+/// round as many times as it takes to close up, see [`Hypotrochoid::revolutions`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hypotrochoid {
+    pub centre: Point,
+    pub fixed_radius: f32,
+    pub rolling_radius: f32,
+    pub pen: f32,
+    /// the number of times round the fixed circle before the curve closes up, worked out from the radii by
+    /// [`Hypotrochoid::new`] - see [`revolutions`]
+    pub revolutions: f32,
+}
+
+impl Hypotrochoid {
+    pub fn new(centre: Point, fixed_radius: f32, rolling_radius: f32, pen: f32) -> Self {
+        Self {
+            centre,
+            fixed_radius,
+            rolling_radius,
+            pen,
+            revolutions: revolutions(fixed_radius, rolling_radius),
+        }
+    }
+
+    /// the hypocycloid, with the pen on the rim of the rolling circle
+    pub fn hypocycloid(centre: Point, fixed_radius: f32, rolling_radius: f32) -> Self {
+        Self::new(centre, fixed_radius, rolling_radius, rolling_radius)
+    }
+}
+
+impl ParametricFunction2D for Hypotrochoid {
+    fn evaluate(&self, t: T) -> Point {
+        let theta = t.value() * self.revolutions * std::f32::consts::TAU;
+        let difference = self.fixed_radius - self.rolling_radius;
+        let (sin, cos) = theta.sin_cos();
+        let (pen_sin, pen_cos) = (difference / self.rolling_radius * theta).sin_cos();
+        (
+            self.centre.x + difference * cos + self.pen * pen_cos,
+            self.centre.y + difference * sin - self.pen * pen_sin,
+        )
+            .into()
+    }
+}
+
+/// The involute of the circle of `base_radius` around `centre` - the curve traced by the end of a taut string as it
+/// unwinds anticlockwise from the circle, starting at the angle `start_angle` (in "turns") and unwinding through
+/// `turns` turns. This is the shape of the flanks of gear teeth.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Involute {
+    pub centre: Point,
+    pub base_radius: f32,
+    pub start_angle: f32,
+    pub turns: f32,
+}
+
+impl Involute {
+    pub fn new(centre: Point, base_radius: f32, start_angle: f32, turns: f32) -> Self {
+        Self {
+            centre,
+            base_radius,
+            start_angle,
+            turns,
+        }
+    }
+
+    /// returns how many turns of string have to unwind to reach `radius` from the centre - zero inside the base
+    /// circle. Handy for cutting a gear tooth off at its tip circle.
+    pub fn turns_to_radius(&self, radius: f32) -> f32 {
+        let ratio = radius / self.base_radius;
+        (ratio * ratio - 1.0).max(0.0).sqrt() / std::f32::consts::TAU
+    }
+}
+
+impl ParametricFunction2D for Involute {
+    fn evaluate(&self, t: T) -> Point {
+        let unwound = t.value() * self.turns * std::f32::consts::TAU;
+        let angle = self.start_angle * std::f32::consts::TAU + unwound;
+        let (sin, cos) = angle.sin_cos();
+        (
+            self.centre.x + self.base_radius * (cos + unwound * sin),
+            self.centre.y + self.base_radius * (sin - unwound * cos),
+        )
+            .into()
+    }
+}
+
+/// the number of times a circle of `rolling_radius` has to go round one of `fixed_radius` to get back to where it
+/// started - the denominator of their ratio as a fraction in lowest terms. Ratios that aren't close to a fraction with
+/// denominator up to 1000 never quite close, and one revolution is used.
+pub fn revolutions(fixed_radius: f32, rolling_radius: f32) -> f32 {
+    as_fraction(fixed_radius / rolling_radius, 1000).map_or(1.0, |(_, d)| d as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_cycloid() {
+        let c = Cycloid::new((1.0, 1.0).into(), 1.0, 2.0);
+
+        let res = c.start();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        // the top of the first arch
+        let res = c.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0 + PI, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 3.0, epsilon = 1e-6);
+
+        let res = c.end();
+        assert_relative_eq!(res.x, 1.0 + 4.0 * PI, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+
+        // each arch is 8 times the radius long
+        assert_relative_eq!(c.arc_length(1e-5), 16.0, epsilon = 1e-2);
+
+        // curtate ones stay off the line and prolate ones dip below it
+        let c = Cycloid::trochoid((0.0, 0.0).into(), 1.0, 0.5, 1.0);
+        assert_relative_eq!(c.start().y, 0.5);
+        let c = Cycloid::trochoid((0.0, 0.0).into(), 1.0, 2.0, 1.0);
+        assert_relative_eq!(c.start().y, -1.0);
+        assert_relative_eq!(c.evaluate(T::new(0.5)).y, 3.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_epitrochoid() {
+        // a cardioid
+        let e = Epitrochoid::epicycloid((1.0, 1.0).into(), 1.0, 1.0);
+        assert_relative_eq!(e.revolutions, 1.0);
+
+        let res = e.start();
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = e.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, -2.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-5);
+
+        let e = Epitrochoid::new((0.0, 0.0).into(), 5.0, 3.0, 1.0);
+        assert_relative_eq!(e.revolutions, 3.0);
+        let (a, b) = (e.start(), e.end());
+        assert_relative_eq!(a.x, 7.0);
+        assert_relative_eq!(a.x, b.x, epsilon = 1e-4);
+        assert_relative_eq!(a.y, b.y, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_hypotrochoid() {
+        // an astroid, with cusps on the axes
+        let h = Hypotrochoid::hypocycloid((0.0, 0.0).into(), 4.0, 1.0);
+        assert_relative_eq!(h.revolutions, 1.0);
+
+        let res = h.start();
+        assert_relative_eq!(res.x, 4.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = h.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 4.0, epsilon = 1e-5);
+
+        let h = Hypotrochoid::new((0.0, 0.0).into(), 5.0, 3.0, 5.0);
+        assert_relative_eq!(h.revolutions, 3.0);
+        let (a, b) = (h.start(), h.end());
+        assert_relative_eq!(a.x, 7.0);
+        assert_relative_eq!(a.x, b.x, epsilon = 1e-4);
+        assert_relative_eq!(a.y, b.y, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_involute() {
+        let i = Involute::new((1.0, 1.0).into(), 1.0, 0.25, 0.5);
+
+        let res = i.start();
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 2.0, epsilon = 1e-6);
+
+        // a quarter turn unwound leaves a quarter of the circumference of string sticking out
+        let res = i.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0 + PI / 2.0, epsilon = 1e-5);
+
+        // and the string stays at a tangent to the circle
+        let res = i.end();
+        assert_relative_eq!(res.x, 1.0 - PI, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 1.0 - 1.0, epsilon = 1e-5);
+
+        let turns = i.turns_to_radius(2.0);
+        let i = Involute::new((0.0, 0.0).into(), 1.0, 0.0, turns);
+        assert_relative_eq!(i.end().to_vector().length(), 2.0, epsilon = 1e-5);
+        assert_relative_eq!(i.turns_to_radius(0.5), 0.0);
+    }
+}
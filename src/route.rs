@@ -0,0 +1,231 @@
+//! Routing paths around obstacles
+
+use std::rc::Rc;
+
+use crate::{
+    bezier::BezierThirdSpline,
+    core::{ParametricFunction2D, Point},
+    geometry::{closest_on_polyline, segment_segment_distance},
+};
+
+/// Number of samples used to approximate each obstacle as a polyline
+const OBSTACLE_SAMPLES: usize = 32;
+
+/// Number of relaxation passes applied to the elastic band
+const BAND_ITERATIONS: usize = 64;
+
+/// Computes a smooth path from `start` to `end` which stays at least `clearance` away from every one of the
+/// `obstacles`. A shortest path is first found on a visibility graph built around the (sampled) obstacles and
+/// is then relaxed as an elastic band, the result is a [`BezierThirdSpline`] through the relaxed band.
+///
+/// Returns `None` if either endpoint is within `clearance` of an obstacle or no route exists.
+pub fn route_around(
+    start: Point,
+    end: Point,
+    obstacles: &[Rc<Box<dyn ParametricFunction2D>>],
+    clearance: f32,
+) -> Option<BezierThirdSpline> {
+    let polylines: Vec<Vec<Point>> = obstacles
+        .iter()
+        .map(|o| o.linspace(OBSTACLE_SAMPLES))
+        .collect();
+
+    if distance_to_polylines(start, &polylines) < clearance
+        || distance_to_polylines(end, &polylines) < clearance
+    {
+        return None;
+    }
+
+    let path = shortest_path(start, end, &polylines, clearance)?;
+    let band = relax_band(&path, &polylines, clearance);
+
+    Some(BezierThirdSpline::smooth_through(&band, 0.0))
+}
+
+/// Candidate waypoints, offset from each side of every obstacle sample
+fn waypoints(polylines: &[Vec<Point>], clearance: f32) -> Vec<Point> {
+    // a little extra room so that edges between neighbouring waypoints remain clear
+    let offset = clearance * 1.5;
+    let mut points = vec![];
+
+    for polyline in polylines {
+        let n = polyline.len();
+        for i in 0..n {
+            let prev = polyline[i.saturating_sub(1)];
+            let next = polyline[(i + 1).min(n - 1)];
+            let dir = next - prev;
+            let len = dir.length();
+            if len <= f32::EPSILON {
+                continue;
+            }
+            let (tx, ty) = (dir.x / len, dir.y / len);
+            let p = polyline[i];
+
+            points.push((p.x - ty * offset, p.y + tx * offset).into());
+            points.push((p.x + ty * offset, p.y - tx * offset).into());
+
+            // allow routing around the loose ends of open curves
+            if i == 0 {
+                points.push((p.x - tx * offset, p.y - ty * offset).into());
+            }
+            if i == n - 1 {
+                points.push((p.x + tx * offset, p.y + ty * offset).into());
+            }
+        }
+    }
+
+    points
+        .into_iter()
+        .filter(|&p| distance_to_polylines(p, polylines) >= clearance)
+        .collect()
+}
+
+/// Dijkstra over the visibility graph of `start`, `end` and the waypoints
+fn shortest_path(
+    start: Point,
+    end: Point,
+    polylines: &[Vec<Point>],
+    clearance: f32,
+) -> Option<Vec<Point>> {
+    let mut nodes = vec![start, end];
+    nodes.extend(waypoints(polylines, clearance));
+
+    let n = nodes.len();
+    let mut dist = vec![f32::INFINITY; n];
+    let mut prev: Vec<Option<usize>> = vec![None; n];
+    let mut done = vec![false; n];
+    dist[0] = 0.0;
+
+    loop {
+        let current = (0..n)
+            .filter(|&i| !done[i] && dist[i].is_finite())
+            .min_by(|&a, &b| dist[a].total_cmp(&dist[b]))?;
+
+        if current == 1 {
+            break;
+        }
+        done[current] = true;
+
+        for next in 0..n {
+            if done[next] {
+                continue;
+            }
+            let d = dist[current] + (nodes[next] - nodes[current]).length();
+            if d < dist[next] && is_clear(nodes[current], nodes[next], polylines, clearance) {
+                dist[next] = d;
+                prev[next] = Some(current);
+            }
+        }
+    }
+
+    let mut path = vec![end];
+    let mut current = 1;
+    while let Some(p) = prev[current] {
+        path.push(nodes[p]);
+        current = p;
+    }
+    path.reverse();
+
+    Some(path)
+}
+
+/// Resamples the path and repeatedly contracts it, pushing points back out of the obstacles' clearance zones
+fn relax_band(path: &[Point], polylines: &[Vec<Point>], clearance: f32) -> Vec<Point> {
+    let spacing = (clearance * 0.5).max(f32::EPSILON);
+    let mut band = vec![path[0]];
+    for pair in path.windows(2) {
+        let steps = ((pair[1] - pair[0]).length() / spacing).ceil().max(1.0) as usize;
+        for i in 1..=steps {
+            band.push(pair[0].lerp(pair[1], i as f32 / steps as f32));
+        }
+    }
+
+    for _ in 0..BAND_ITERATIONS {
+        for i in 1..band.len() - 1 {
+            let target = band[i - 1].lerp(band[i + 1], 0.5);
+            let mut p = band[i].lerp(target, 0.5);
+
+            for polyline in polylines {
+                let q = closest_on_polyline(p, polyline);
+                let away = p - q;
+                let d = away.length();
+                if d < clearance && d > f32::EPSILON {
+                    p = q + away * (clearance / d);
+                }
+            }
+
+            // only accept moves which keep the band clear of the obstacles
+            if is_clear(band[i - 1], p, polylines, clearance * 0.99)
+                && is_clear(p, band[i + 1], polylines, clearance * 0.99)
+            {
+                band[i] = p;
+            }
+        }
+    }
+
+    band
+}
+
+fn is_clear(a: Point, b: Point, polylines: &[Vec<Point>], clearance: f32) -> bool {
+    polylines.iter().all(|polyline| {
+        polyline
+            .windows(2)
+            .all(|s| segment_segment_distance(a, b, s[0], s[1]) >= clearance)
+    })
+}
+
+fn distance_to_polylines(p: Point, polylines: &[Vec<Point>]) -> f32 {
+    polylines
+        .iter()
+        .map(|polyline| (p - closest_on_polyline(p, polyline)).length())
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{circle::Circle, segment::Segment};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_route_unobstructed() {
+        let route = route_around((0.0, 0.0).into(), (10.0, 0.0).into(), &[], 1.0).unwrap();
+
+        let s = route.start();
+        assert_relative_eq!(s.x, 0.0);
+        assert_relative_eq!(s.y, 0.0);
+
+        let e = route.end();
+        assert_relative_eq!(e.x, 10.0);
+        assert_relative_eq!(e.y, 0.0);
+
+        for p in route.linspace(20) {
+            assert_relative_eq!(p.y, 0.0, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_route_around_circle() {
+        let obstacle: Rc<Box<dyn ParametricFunction2D>> =
+            Rc::new(Box::new(Circle::new((5.0, 0.0).into(), 2.0, None)));
+        let polyline = obstacle.linspace(256);
+        let route = route_around((0.0, 0.0).into(), (10.0, 0.0).into(), &[obstacle], 0.5).unwrap();
+
+        let e = route.end();
+        assert_relative_eq!(e.x, 10.0, epsilon = 1e-4);
+        assert_relative_eq!(e.y, 0.0, epsilon = 1e-4);
+
+        for p in route.linspace(200) {
+            assert!((p - closest_on_polyline(p, &polyline)).length() > 0.25);
+        }
+    }
+
+    #[test]
+    fn test_route_blocked_endpoint() {
+        let obstacle: Rc<Box<dyn ParametricFunction2D>> = Rc::new(Box::new(Segment::new(
+            (0.0, -1.0).into(),
+            (0.0, 1.0).into(),
+        )));
+        assert!(route_around((0.0, 0.0).into(), (10.0, 0.0).into(), &[obstacle], 0.5).is_none());
+    }
+}
@@ -1,8 +1,12 @@
 //! Line segments from point to point
 
-use crate::core::{ParametricFunction2D, Point, T};
+use crate::{
+    core::{ParametricFunction2D, Point, T},
+    error::{check_points, ParametricsError},
+};
 
 /// A line segment from a start point to an end point
+#[derive(Debug, Clone, PartialEq)]
 pub struct Segment {
     pub start: Point,
     pub end: Point,
@@ -10,19 +14,30 @@ pub struct Segment {
 
 impl Segment {
     pub fn new(start: Point, end: Point) -> Self {
-        Self {
-            start: start,
-            end: end,
+        Self { start, end }
+    }
+
+    /// as [`Segment::new`] but fails if either point isn't finite or they are the same point
+    pub fn try_new(start: Point, end: Point) -> Result<Self, ParametricsError> {
+        check_points(&[start, end])?;
+        if start == end {
+            return Err(ParametricsError::ZeroLength);
         }
+        Ok(Self::new(start, end))
     }
 }
 
 impl ParametricFunction2D for Segment {
     fn evaluate(&self, t: T) -> Point {
+        self.evaluate_unclamped(t.value())
+    }
+
+    /// carries on along the line
+    fn evaluate_unclamped(&self, t: f32) -> Point {
         let dir = (-self.start.x + self.end.x, -self.start.y + self.end.y);
         let start = self.start;
 
-        (start.x + t.value() * dir.0, start.y + t.value() * dir.1).into()
+        (start.x + t * dir.0, start.y + t * dir.1).into()
     }
 }
 
@@ -39,4 +54,18 @@ mod tests {
         assert_relative_eq!(res.x, 0.5);
         assert_relative_eq!(res.y, 1.0);
     }
+
+    #[test]
+    fn test_try_new() {
+        let a = (0.0, 0.0).into();
+        assert!(Segment::try_new(a, (1.0, 2.0).into()).is_ok());
+        assert_eq!(
+            Segment::try_new(a, a).err(),
+            Some(ParametricsError::ZeroLength)
+        );
+        assert_eq!(
+            Segment::try_new(a, (f32::NAN, 0.0).into()).err(),
+            Some(ParametricsError::NotFinite)
+        );
+    }
 }
@@ -0,0 +1,318 @@
+//! Spirals, including the Euler spiral (clothoid)
+
+use crate::{
+    core::{ParametricFunction2D, Point, Vector, T},
+    ellipse::EllipseArc,
+};
+
+/// How the radius of a [`Spiral`] grows with the angle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpiralKind {
+    /// `r = a + b * angle`
+    Archimedean,
+    /// `r = a * exp(b * angle)`
+    Logarithmic,
+}
+
+/// A spiral around `centre` winding anticlockwise through `turns` turns from the positive x-axis, its radius
+/// growing with the angle (in radians) as given by `kind`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spiral {
+    pub centre: Point,
+    pub a: f32,
+    pub b: f32,
+    pub turns: f32,
+    pub kind: SpiralKind,
+}
+
+impl Spiral {
+    /// the spiral whose radius starts at `a` and grows by `b` per radian, so successive turns are evenly spaced
+    pub fn archimedean(centre: Point, a: f32, b: f32, turns: f32) -> Self {
+        Self {
+            centre,
+            a,
+            b,
+            turns,
+            kind: SpiralKind::Archimedean,
+        }
+    }
+
+    /// the spiral whose radius starts at `a` and grows by a factor of `exp(k)` per radian, so it keeps the same shape
+    /// as it grows
+    pub fn logarithmic(centre: Point, a: f32, k: f32, turns: f32) -> Self {
+        Self {
+            centre,
+            a,
+            b: k,
+            turns,
+            kind: SpiralKind::Logarithmic,
+        }
+    }
+
+    /// returns the radius at `angle` radians
+    pub fn radius(&self, angle: f32) -> f32 {
+        match self.kind {
+            SpiralKind::Archimedean => self.a + self.b * angle,
+            SpiralKind::Logarithmic => self.a * (self.b * angle).exp(),
+        }
+    }
+}
+
+impl ParametricFunction2D for Spiral {
+    fn evaluate(&self, t: T) -> Point {
+        let angle = t.value() * self.turns * std::f32::consts::TAU;
+        let r = self.radius(angle);
+        let (sin, cos) = angle.sin_cos();
+        (self.centre.x + r * cos, self.centre.y + r * sin).into()
+    }
+}
+
+/// A clothoid (Euler spiral) - a curve whose curvature grows linearly with the distance along it, which makes it
+/// the smooth way to ease from a straight into a bend. It sets off from `start` heading in `direction` (in "turns")
+/// with zero curvature, which then grows by `curvature_rate` per unit of distance (positive bends to the left) over
+/// its `length`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clothoid {
+    pub start: Point,
+    pub direction: f32,
+    pub curvature_rate: f32,
+    pub length: f32,
+}
+
+impl Clothoid {
+    pub fn new(start: Point, direction: f32, curvature_rate: f32, length: f32) -> Self {
+        Self {
+            start,
+            direction,
+            curvature_rate,
+            length,
+        }
+    }
+
+    /// the clothoid easing from a straight line heading in `direction` into a circular arc of `radius` over `length`,
+    /// bending to the left for a positive radius and to the right for a negative one. See [`Clothoid::arc_after`]
+    /// for the arc itself.
+    pub fn transition(start: Point, direction: f32, radius: f32, length: f32) -> Self {
+        Self::new(start, direction, 1.0 / (radius * length), length)
+    }
+
+    /// returns the curvature at the end
+    pub fn end_curvature(&self) -> f32 {
+        self.curvature_rate * self.length
+    }
+
+    /// returns the heading at the end, in "turns"
+    pub fn end_direction(&self) -> f32 {
+        self.direction
+            + self.curvature_rate * self.length * self.length / 2.0 / std::f32::consts::TAU
+    }
+
+    /// returns the circular arc carrying on smoothly from the end with the end curvature, through `sweep` turns -
+    /// `None` if there is no curvature to carry on with
+    pub fn arc_after(&self, sweep: f32) -> Option<EllipseArc> {
+        let curvature = self.end_curvature();
+        if curvature == 0.0 {
+            return None;
+        }
+
+        let radius = 1.0 / curvature.abs();
+        let heading = self.end_direction() * std::f32::consts::TAU;
+        let left = Vector::new(-heading.sin(), heading.cos());
+        let centre = self.end() + left * radius * curvature.signum();
+
+        let offset = self.end() - centre;
+        let start_angle = offset.y.atan2(offset.x) / std::f32::consts::TAU;
+        Some(EllipseArc::new(
+            centre,
+            radius,
+            radius,
+            0.0,
+            start_angle,
+            start_angle + sweep * curvature.signum(),
+        ))
+    }
+}
+
+impl ParametricFunction2D for Clothoid {
+    fn evaluate(&self, t: T) -> Point {
+        let s = t.value() * self.length;
+        let (sin, cos) = (self.direction * std::f32::consts::TAU).sin_cos();
+
+        let (x, y) = if self.curvature_rate == 0.0 {
+            (s, 0.0)
+        } else {
+            // scaled so that the heading at u is pi u^2 / 2, as in the Fresnel integrals
+            let scale = (std::f64::consts::PI / self.curvature_rate.abs() as f64).sqrt();
+            let (c, s) = fresnel(s as f64 / scale);
+            (
+                (scale * c) as f32,
+                (scale * s) as f32 * self.curvature_rate.signum(),
+            )
+        };
+
+        (
+            self.start.x + x * cos - y * sin,
+            self.start.y + x * sin + y * cos,
+        )
+            .into()
+    }
+}
+
+/// the Fresnel integrals `(C(x), S(x))` - the integrals from 0 to `x` of `cos(pi u^2 / 2)` and `sin(pi u^2 / 2)`,
+/// from their power series for small `x` and a continued fraction otherwise (as in Numerical Recipes)
+fn fresnel(x: f64) -> (f64, f64) {
+    use std::f64::consts::{FRAC_PI_2, PI};
+    const EPS: f64 = 1e-12;
+
+    let ax = x.abs();
+    let (c, s) = if ax < 1.5 {
+        let (mut c, mut s) = (0.0, 0.0);
+        // the terms of the series for C and S interleave, the nth being x^(2n+1) (pi / 2)^n / n! up to sign
+        let mut term = ax;
+        for n in 0..100 {
+            let value = term / (2 * n + 1) as f64;
+            let sign = if (n / 2) % 2 == 0 { 1.0 } else { -1.0 };
+            if n % 2 == 0 {
+                c += sign * value;
+            } else {
+                s += sign * value;
+            }
+            if value < EPS * c.abs().max(s.abs()) {
+                break;
+            }
+            term *= FRAC_PI_2 * ax * ax / (n + 1) as f64;
+        }
+        (c, s)
+    } else {
+        // complex arithmetic on (re, im) pairs
+        let mul = |(a, b): (f64, f64), (c, d): (f64, f64)| (a * c - b * d, a * d + b * c);
+        let div = |(a, b): (f64, f64), (c, d): (f64, f64)| {
+            let den = c * c + d * d;
+            ((a * c + b * d) / den, (b * c - a * d) / den)
+        };
+        let add = |(a, b): (f64, f64), (c, d): (f64, f64)| (a + c, b + d);
+
+        let pix2 = PI * ax * ax;
+        let mut b = (1.0, -pix2);
+        let mut cc = (1.0 / f64::MIN_POSITIVE, 0.0);
+        let mut d = div((1.0, 0.0), b);
+        let mut h = d;
+        let mut n = -1.0;
+        for _ in 2..100 {
+            n += 2.0;
+            let a = -n * (n + 1.0);
+            b = add(b, (4.0, 0.0));
+            d = div((1.0, 0.0), add(mul((a, 0.0), d), b));
+            cc = add(b, div((a, 0.0), cc));
+            let del = mul(cc, d);
+            h = mul(h, del);
+            if (del.0 - 1.0).abs() + del.1.abs() < EPS {
+                break;
+            }
+        }
+        h = mul(h, (ax, -ax));
+        let (cos, sin) = ((0.5 * pix2).cos(), (0.5 * pix2).sin());
+        mul((0.5, 0.5), add((1.0, 0.0), mul((-cos, -sin), h)))
+    };
+
+    if x < 0.0 {
+        (-c, -s)
+    } else {
+        (c, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::f32::consts::TAU;
+
+    #[test]
+    fn test_archimedean_spiral() {
+        let s = Spiral::archimedean((1.0, 0.0).into(), 0.5, 1.0 / TAU, 3.0);
+
+        let res = s.start();
+        assert_relative_eq!(res.x, 1.5);
+        assert_relative_eq!(res.y, 0.0);
+
+        // one more unit of radius each turn
+        let res = s.evaluate(T::new(1.0 / 3.0));
+        assert_relative_eq!(res.x, 2.5, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-5);
+
+        let res = s.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0 - 2.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-5);
+
+        let res = s.end();
+        assert_relative_eq!(res.x, 1.0 + 3.5, epsilon = 1e-5);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_logarithmic_spiral() {
+        // the golden spiral grows by the golden ratio every quarter turn
+        let phi = (1.0 + f32::sqrt(5.0)) / 2.0;
+        let s = Spiral::logarithmic((0.0, 0.0).into(), 1.0, phi.ln() / (TAU / 4.0), 2.0);
+
+        let res = s.start();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = s.evaluate(T::new(0.125));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(res.y, phi, epsilon = 1e-5);
+
+        let res = s.end();
+        assert_relative_eq!(res.x, phi.powi(8), epsilon = 1e-3);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_fresnel() {
+        for (x, c, s) in [
+            (0.5, 0.4923442, 0.0647324),
+            (1.0, 0.7798934, 0.4382591),
+            (2.0, 0.4882534, 0.3434157),
+            (5.0, 0.5636312, 0.4991914),
+            (-1.0, -0.7798934, -0.4382591),
+        ] {
+            let (res_c, res_s) = fresnel(x);
+            assert_relative_eq!(res_c, c, epsilon = 1e-6);
+            assert_relative_eq!(res_s, s, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_clothoid() {
+        let c = Clothoid::transition((0.0, 0.0).into(), 0.0, 2.0, 1.0);
+
+        // the curvature, and so the rate of turning, grows along it
+        assert_relative_eq!(c.end_curvature(), 0.5);
+        assert_relative_eq!(c.end_direction(), 0.25 / TAU);
+        let tangent = c.tangent(T::start());
+        assert_relative_eq!(tangent.y, 0.0, epsilon = 1e-3);
+        let tangent = c.tangent(T::end());
+        assert_relative_eq!(tangent.y.atan2(tangent.x), 0.25, epsilon = 1e-3);
+        assert_relative_eq!(c.arc_length(1e-5), 1.0, epsilon = 1e-4);
+
+        // the arc picks up where it leaves off
+        let arc = c.arc_after(0.25).unwrap();
+        assert_relative_eq!(arc.radius_x, 2.0);
+        let (a, b) = (c.end(), arc.start());
+        assert_relative_eq!(a.x, b.x, epsilon = 1e-5);
+        assert_relative_eq!(a.y, b.y, epsilon = 1e-5);
+        let (a, b) = (c.tangent(T::end()), arc.tangent(T::start()));
+        assert_relative_eq!(a.x, b.x, epsilon = 1e-3);
+        assert_relative_eq!(a.y, b.y, epsilon = 1e-3);
+
+        // bending to the right instead
+        let c = Clothoid::transition((0.0, 0.0).into(), 0.25, -2.0, 1.0);
+        let res = c.end();
+        assert!(res.x > 0.0);
+        assert!(Clothoid::new((0.0, 0.0).into(), 0.0, 0.0, 1.0)
+            .arc_after(0.25)
+            .is_none());
+    }
+}
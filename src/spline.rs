@@ -0,0 +1,425 @@
+//! Splines that pass through the points they're given
+
+use crate::{
+    bezier::BezierThird,
+    continuity::{continuity_breaks, ContinuityBreak},
+    core::{ParametricFunction1D, ParametricFunction2D, Point, Vector, T},
+    error::ParametricsError,
+};
+
+/// A Catmull-Rom spline through `points` in order, each span between neighbouring points taking an equal share of
+/// `t`. `alpha` sets how the spans are parameterised - 0 is uniform, 0.5 centripetal (which never forms cusps or
+/// loops within a span) and 1 chordal. The ends are extended by mirroring the second and second to last points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRom {
+    pub points: Vec<Point>,
+    pub alpha: f32,
+}
+
+impl CatmullRom {
+    pub fn new(points: Vec<Point>, alpha: f32) -> Self {
+        Self { points, alpha }
+    }
+
+    /// the uniform spline, with `alpha` 0
+    pub fn uniform(points: Vec<Point>) -> Self {
+        Self::new(points, 0.0)
+    }
+
+    /// the centripetal spline, with `alpha` 0.5
+    pub fn centripetal(points: Vec<Point>) -> Self {
+        Self::new(points, 0.5)
+    }
+
+    /// the chordal spline, with `alpha` 1
+    pub fn chordal(points: Vec<Point>) -> Self {
+        Self::new(points, 1.0)
+    }
+}
+
+impl ParametricFunction2D for CatmullRom {
+    fn evaluate(&self, t: T) -> Point {
+        let n = self.points.len();
+        if n < 2 {
+            return self.points.first().copied().unwrap_or_else(Point::origin);
+        }
+
+        let position = t.value() * (n - 1) as f32;
+        let i = (position.floor() as usize).min(n - 2);
+        let s = position - i as f32;
+
+        let p1 = self.points[i];
+        let p2 = self.points[i + 1];
+        let p0 = if i > 0 {
+            self.points[i - 1]
+        } else {
+            p1 + (p1 - p2)
+        };
+        let p3 = if i + 2 < n {
+            self.points[i + 2]
+        } else {
+            p2 + (p2 - p1)
+        };
+
+        // the Barry-Goldman pyramid over the knots spaced by distance to the power alpha
+        let knot = |a: Point, b: Point| (b - a).length().powf(self.alpha).max(1e-6);
+        let t0 = 0.0;
+        let t1 = t0 + knot(p0, p1);
+        let t2 = t1 + knot(p1, p2);
+        let t3 = t2 + knot(p2, p3);
+        let u = t1 + (t2 - t1) * s;
+
+        let lerp = |a: Point, b: Point, ta: f32, tb: f32| a.lerp(b, (u - ta) / (tb - ta));
+        let a1 = lerp(p0, p1, t0, t1);
+        let a2 = lerp(p1, p2, t1, t2);
+        let a3 = lerp(p2, p3, t2, t3);
+        let b1 = lerp(a1, a2, t0, t2);
+        let b2 = lerp(a2, a3, t1, t3);
+        lerp(b1, b2, t1, t2)
+    }
+}
+
+/// A cubic Hermite spline through `points` in order, with the matching `tangents` as its derivatives there - each
+/// span between neighbouring points takes an equal share of `t`, and the tangents are with respect to the position
+/// along the span (so a tangent as long as the span gives a gentle curve).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hermite {
+    pub points: Vec<Point>,
+    pub tangents: Vec<Vector>,
+}
+
+impl Hermite {
+    /// fails unless there's one tangent for every point
+    pub fn new(points: Vec<Point>, tangents: Vec<Vector>) -> Result<Self, ParametricsError> {
+        if points.len() != tangents.len() {
+            return Err(ParametricsError::LengthMismatch {
+                expected: points.len(),
+                found: tangents.len(),
+            });
+        }
+        Ok(Self { points, tangents })
+    }
+
+    /// returns the spans between neighbouring points in order, as the cubic Beziers they are
+    pub fn segments(&self) -> impl Iterator<Item = BezierThird> + '_ {
+        self.points
+            .windows(2)
+            .zip(self.tangents.windows(2))
+            .map(|(p, m)| BezierThird::new(p[0], p[1], p[0] + m[0] / 3.0, p[1] - m[1] / 3.0))
+    }
+
+    /// returns the joins between the pieces that aren't smooth to within `tolerance`, see [`ContinuityBreak`]
+    pub fn analyze_continuity(&self, tolerance: f32) -> Vec<ContinuityBreak> {
+        let segments: Vec<_> = self.segments().collect();
+        continuity_breaks(
+            segments.iter().map(|s| s as &dyn ParametricFunction2D),
+            false,
+            tolerance,
+        )
+    }
+}
+
+impl ParametricFunction2D for Hermite {
+    fn evaluate(&self, t: T) -> Point {
+        let n = self.points.len().min(self.tangents.len());
+        if n < 2 {
+            return self.points.first().copied().unwrap_or_else(Point::origin);
+        }
+
+        let position = t.value() * (n - 1) as f32;
+        let i = (position.floor() as usize).min(n - 2);
+        hermite(
+            self.points[i],
+            self.tangents[i],
+            self.points[i + 1],
+            self.tangents[i + 1],
+            position - i as f32,
+        )
+    }
+}
+
+/// An Akima spline through `values` at the increasing `knots`, as a function of `t` running from the first knot to
+/// the last. Its slope at each knot is picked from the slopes of the neighbouring chords so that a single outlier
+/// only disturbs the nearby spans, without the overshoot and ringing of a natural cubic spline. Given at least two
+/// knots, see [`Akima1D::value_at`] for evaluating it at a knot position directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Akima1D {
+    pub knots: Vec<f32>,
+    pub values: Vec<f32>,
+}
+
+impl Akima1D {
+    /// fails unless there are at least two knots, increasing, with a value for each
+    pub fn new(knots: Vec<f32>, values: Vec<f32>) -> Result<Self, ParametricsError> {
+        if knots.len() != values.len() {
+            return Err(ParametricsError::LengthMismatch {
+                expected: knots.len(),
+                found: values.len(),
+            });
+        }
+        if knots.len() < 2 {
+            return Err(ParametricsError::TooFewPoints {
+                needed: 2,
+                found: knots.len(),
+            });
+        }
+        if knots.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(ParametricsError::InvalidKnots);
+        }
+        Ok(Self { knots, values })
+    }
+
+    /// the spline through `values` at evenly spaced knots, so each span takes an equal share of `t`
+    pub fn evenly_spaced(values: Vec<f32>) -> Self {
+        Self {
+            knots: (0..values.len()).map(|i| i as f32).collect(),
+            values,
+        }
+    }
+
+    /// returns the value at `x`, which is clamped to the range of the knots
+    pub fn value_at(&self, x: f32) -> f32 {
+        let n = self.knots.len().min(self.values.len());
+        akima(n, |i| self.knots[i], |i| self.values[i], x)
+    }
+}
+
+impl ParametricFunction1D for Akima1D {
+    fn evaluate(&self, t: T) -> f32 {
+        match (self.knots.first(), self.knots.last()) {
+            (Some(&first), Some(&last)) => self.value_at(first + (last - first) * t.value()),
+            _ => 0.0,
+        }
+    }
+}
+
+/// An Akima spline through `points` in order, with each coordinate interpolated as an [`Akima1D`] and each span
+/// between neighbouring points taking an equal share of `t` - good for noisy traced data, as an outlier only bends
+/// the curve nearby
+#[derive(Debug, Clone, PartialEq)]
+pub struct Akima {
+    pub points: Vec<Point>,
+}
+
+impl Akima {
+    pub fn new(points: Vec<Point>) -> Self {
+        Self { points }
+    }
+}
+
+impl ParametricFunction2D for Akima {
+    fn evaluate(&self, t: T) -> Point {
+        let n = self.points.len();
+        let x = t.value() * n.saturating_sub(1) as f32;
+        let knot = |i: usize| i as f32;
+        (
+            akima(n, knot, |i| self.points[i].x, x),
+            akima(n, knot, |i| self.points[i].y, x),
+        )
+            .into()
+    }
+}
+
+/// evaluates the Akima spline through the `n` values given by `value` at the knots given by `knot` at `x`, clamped to
+/// the range of the knots - taking them by index saves collecting coordinates on every evaluation
+fn akima(n: usize, knot: impl Fn(usize) -> f32, value: impl Fn(usize) -> f32, x: f32) -> f32 {
+    if n < 2 {
+        return if n == 1 { value(0) } else { 0.0 };
+    }
+
+    // the slope of chord `k`, extended two chords beyond each end by carrying on the change in slope
+    let chord = |k: usize| (value(k + 1) - value(k)) / (knot(k + 1) - knot(k));
+    let slope = |k: isize| -> f32 {
+        let last = n as isize - 2;
+        if n == 2 {
+            return chord(0);
+        }
+        match k {
+            k if k < -1 => 3.0 * chord(0) - 2.0 * chord(1),
+            -1 => 2.0 * chord(0) - chord(1),
+            k if k > last + 1 => 3.0 * chord(last as usize) - 2.0 * chord(last as usize - 1),
+            k if k == last + 1 => 2.0 * chord(last as usize) - chord(last as usize - 1),
+            k => chord(k as usize),
+        }
+    };
+    let tangent = |i: usize| {
+        let i = i as isize;
+        let (m0, m1, m2, m3) = (slope(i - 2), slope(i - 1), slope(i), slope(i + 1));
+        let (w1, w2) = ((m3 - m2).abs(), (m1 - m0).abs());
+        if w1 + w2 == 0.0 {
+            (m1 + m2) / 2.0
+        } else {
+            (w1 * m1 + w2 * m2) / (w1 + w2)
+        }
+    };
+
+    let x = x.clamp(knot(0), knot(n - 1));
+    let i = (0..n - 1).rposition(|k| knot(k) <= x).unwrap_or(0);
+    let width = knot(i + 1) - knot(i);
+    let s = (x - knot(i)) / width;
+
+    let (s2, s3) = (s * s, s * s * s);
+    let h00 = 2.0 * s3 - 3.0 * s2 + 1.0;
+    let h10 = s3 - 2.0 * s2 + s;
+    let h01 = -2.0 * s3 + 3.0 * s2;
+    let h11 = s3 - s2;
+    h00 * value(i) + h10 * width * tangent(i) + h01 * value(i + 1) + h11 * width * tangent(i + 1)
+}
+
+/// evaluates the cubic going from `p0` with derivative `m0` to `p1` with derivative `m1` at `s`
+fn hermite(p0: Point, m0: Vector, p1: Point, m1: Vector, s: f32) -> Point {
+    let (s2, s3) = (s * s, s * s * s);
+    let h00 = 2.0 * s3 - 3.0 * s2 + 1.0;
+    let h10 = s3 - 2.0 * s2 + s;
+    let h01 = -2.0 * s3 + 3.0 * s2;
+    let h11 = s3 - s2;
+    (p0.to_vector() * h00 + m0 * h10 + p1.to_vector() * h01 + m1 * h11).to_point()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_catmull_rom() {
+        let points: Vec<Point> = vec![
+            (0.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (3.0, 1.0).into(),
+            (4.0, 0.0).into(),
+        ];
+
+        // passes through every point whatever the alpha
+        for alpha in [0.0, 0.5, 1.0] {
+            let c = CatmullRom::new(points.clone(), alpha);
+            for (i, p) in points.iter().enumerate() {
+                let res = c.evaluate(T::new(i as f32 / 3.0));
+                assert_relative_eq!(res.x, p.x, epsilon = 1e-5);
+                assert_relative_eq!(res.y, p.y, epsilon = 1e-5);
+            }
+        }
+
+        // the uniform midpoint is (-p0 + 9 p1 + 9 p2 - p3) / 16
+        let c = CatmullRom::uniform(points.clone());
+        let res = c.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 2.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.125, epsilon = 1e-6);
+
+        // and it's smooth through the points
+        let c = CatmullRom::centripetal(points);
+        let before = c.tangent(T::new(1.0 / 3.0 - 1e-2));
+        let after = c.tangent(T::new(1.0 / 3.0 + 1e-2));
+        assert_relative_eq!(before.x, after.x, epsilon = 0.1);
+        assert_relative_eq!(before.y, after.y, epsilon = 0.1);
+
+        // repeated points don't blow up
+        let c = CatmullRom::chordal(vec![
+            (0.0, 0.0).into(),
+            (0.0, 0.0).into(),
+            (1.0, 0.0).into(),
+        ]);
+        assert!(c
+            .linspace(10)
+            .iter()
+            .all(|p| p.x.is_finite() && p.y.is_finite()));
+    }
+
+    #[test]
+    fn test_hermite() {
+        let points: Vec<Point> = vec![(0.0, 0.0).into(), (2.0, 0.0).into(), (2.0, 2.0).into()];
+        let tangents: Vec<Vector> = vec![(2.0, 0.0).into(), (2.0, 2.0).into(), (0.0, 2.0).into()];
+        let h = Hermite::new(points, tangents).unwrap();
+
+        let res = h.start();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = h.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = h.end();
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 2.0);
+
+        // swinging out below the line to come into the corner heading up and to the right
+        let res = h.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, -0.25, epsilon = 1e-6);
+
+        // the derivative at the points is the tangent scaled by the span's share of t
+        let res = h.derivative(T::start());
+        assert_relative_eq!(res.x, 4.0, epsilon = 1e-2);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-2);
+
+        // each span is a cubic Bezier
+        let segments: Vec<BezierThird> = h.segments().collect();
+        assert_eq!(segments.len(), 2);
+        let res = segments[1].evaluate(T::new(0.3));
+        let expected = h.evaluate(T::new(0.65));
+        assert_relative_eq!(res.x, expected.x, epsilon = 1e-6);
+        assert_relative_eq!(res.y, expected.y, epsilon = 1e-6);
+
+        assert_eq!(
+            Hermite::new(vec![Point::origin()], vec![]).unwrap_err(),
+            ParametricsError::LengthMismatch {
+                expected: 1,
+                found: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_akima_1d() {
+        let a = Akima1D::new(vec![0.0, 1.0, 2.0, 4.0], vec![0.0, 1.0, 2.0, 4.0]).unwrap();
+
+        // reproduces a straight line exactly
+        assert_relative_eq!(a.value_at(3.0), 3.0, epsilon = 1e-6);
+        assert_relative_eq!(a.evaluate(T::new(0.125)), 0.5, epsilon = 1e-6);
+        assert_relative_eq!(a.end(), 4.0);
+        assert_relative_eq!(a.value_at(10.0), 4.0);
+
+        // an outlier only disturbs the spans next to it
+        let a = Akima1D::evenly_spaced(vec![0.0, 0.0, 0.0, 0.0, 5.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_relative_eq!(a.value_at(1.5), 0.0);
+        assert_relative_eq!(a.value_at(6.5), 0.0);
+        assert_relative_eq!(a.value_at(4.0), 5.0);
+        assert!(a.value_at(3.5) > 0.0);
+
+        assert_eq!(
+            Akima1D::new(vec![0.0, 0.0], vec![1.0, 2.0]).unwrap_err(),
+            ParametricsError::InvalidKnots
+        );
+        assert_eq!(
+            Akima1D::new(vec![0.0], vec![1.0]).unwrap_err(),
+            ParametricsError::TooFewPoints {
+                needed: 2,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_akima() {
+        let points: Vec<Point> = vec![
+            (0.0, 0.0).into(),
+            (1.0, 0.0).into(),
+            (2.0, 3.0).into(),
+            (3.0, 0.0).into(),
+            (4.0, 0.0).into(),
+            (5.0, 0.0).into(),
+        ];
+        let a = Akima::new(points.clone());
+
+        for (i, p) in points.iter().enumerate() {
+            let res = a.evaluate(T::new(i as f32 / 5.0));
+            assert_relative_eq!(res.x, p.x, epsilon = 1e-5);
+            assert_relative_eq!(res.y, p.y, epsilon = 1e-5);
+        }
+
+        // flat again straight after the spike, with no ringing
+        let res = a.evaluate(T::new(0.9));
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+    }
+}
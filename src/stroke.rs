@@ -0,0 +1,312 @@
+//! Turning stroked paths into fillable outlines
+
+use std::rc::Rc;
+
+use crate::{
+    core::{Concat, IntoParametric2D, ParametricFunction2D, Point, Reverse, Vector, T},
+    error::ParametricsError,
+    offset::Offset,
+    segment::Segment,
+};
+
+/// How the open ends of a stroke are finished
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineCap {
+    /// squared off exactly at the ends
+    Butt,
+    /// a half circle around each end
+    Round,
+    /// squared off half the width beyond each end
+    Square,
+}
+
+/// How the outside of a corner in a stroke is filled in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineJoin {
+    /// an arc around the corner
+    Round,
+    /// a straight line across the corner
+    Bevel,
+    /// the edges extended until they meet, unless that is more than the given multiple of half the width from the
+    /// corner, in which case it falls back to [`LineJoin::Bevel`]
+    Miter(f32),
+}
+
+/// The outline of the path made up of `functions` stroked with a pen `width` wide, as closed curves that can be
+/// filled. Open paths give a single outline, with `cap` at either end - closed paths (whose end is within
+/// `tolerance` of their start) give an outer and an inner one. `join` is used wherever consecutive functions meet at
+/// an angle, while the edges themselves are [`Offset`]s approximated by cubic Beziers to within `tolerance`.
+///
+/// The outlines are meant to be filled with the nonzero rule - they overlap themselves on the inside of corners,
+/// and curves tighter than half the width fold back on themselves.
+pub fn stroke_outline(
+    functions: &[Rc<Box<dyn ParametricFunction2D>>],
+    width: f32,
+    cap: LineCap,
+    join: LineJoin,
+    tolerance: f32,
+) -> Result<Vec<Concat>, ParametricsError> {
+    if functions.is_empty() {
+        return Err(ParametricsError::Empty);
+    }
+
+    let half = width / 2.0;
+    let closed =
+        (functions[0].start() - functions[functions.len() - 1].end()).length() <= tolerance;
+
+    // the right hand edge is the left hand edge of the path run backwards
+    let reversed: Vec<_> = functions
+        .iter()
+        .rev()
+        .map(|f| Reverse::new(f.clone()).into_parametric())
+        .collect();
+
+    let left = left_edge(functions, half, join, tolerance, closed);
+    let right = left_edge(&reversed, half, join, tolerance, closed);
+
+    if closed {
+        return Ok(vec![Concat::new(left)?, Concat::new(right)?]);
+    }
+
+    let mut outline = left;
+    outline.extend(end_cap(
+        functions[functions.len() - 1].as_ref().as_ref(),
+        half,
+        cap,
+    ));
+    outline.extend(right);
+    outline.extend(end_cap(
+        reversed[reversed.len() - 1].as_ref().as_ref(),
+        half,
+        cap,
+    ));
+    Ok(vec![Concat::new(outline)?])
+}
+
+impl Concat {
+    /// see [`stroke_outline`]
+    pub fn stroke_outline(
+        &self,
+        width: f32,
+        cap: LineCap,
+        join: LineJoin,
+        tolerance: f32,
+    ) -> Result<Vec<Concat>, ParametricsError> {
+        stroke_outline(&self.functions, width, cap, join, tolerance)
+    }
+}
+
+/// the edge `half` to the left of the path, with joins between the functions (including from the last back to the
+/// first if `closed`)
+fn left_edge(
+    functions: &[Rc<Box<dyn ParametricFunction2D>>],
+    half: f32,
+    join: LineJoin,
+    tolerance: f32,
+    closed: bool,
+) -> Vec<Rc<Box<dyn ParametricFunction2D>>> {
+    let mut edge = vec![];
+    for (i, function) in functions.iter().enumerate() {
+        let offset = Offset::new(function.clone(), half);
+        edge.push(offset.to_beziers(tolerance).into_parametric());
+
+        let next = if i + 1 < functions.len() {
+            &functions[i + 1]
+        } else if closed {
+            &functions[0]
+        } else {
+            continue;
+        };
+        edge.extend(corner(
+            function.as_ref().as_ref(),
+            next.as_ref().as_ref(),
+            half,
+            join,
+            tolerance,
+        ));
+    }
+    edge
+}
+
+/// the pieces joining the left edges of `before` and `after` where they meet
+fn corner(
+    before: &dyn ParametricFunction2D,
+    after: &dyn ParametricFunction2D,
+    half: f32,
+    join: LineJoin,
+    tolerance: f32,
+) -> Vec<Rc<Box<dyn ParametricFunction2D>>> {
+    let centre = before.end();
+    let (incoming, outgoing) = (before.tangent(T::end()), after.tangent(T::start()));
+    let from = centre + before.normal(T::end()) * half;
+    let to = centre + after.normal(T::start()) * half;
+    if (to - from).length() <= tolerance {
+        return vec![];
+    }
+
+    // turning left puts this edge on the inside of the corner, where it is just taken through the corner itself
+    let turn = incoming.cross(outgoing);
+    if turn > 0.0 {
+        return vec![
+            Segment::new(from, centre).into_parametric(),
+            Segment::new(centre, to).into_parametric(),
+        ];
+    }
+
+    match join {
+        LineJoin::Round => vec![arc(
+            centre,
+            from,
+            (from - centre).angle_to(to - centre).radians,
+        )],
+        LineJoin::Bevel => vec![Segment::new(from, to).into_parametric()],
+        LineJoin::Miter(limit) => {
+            let along = (to - from).cross(outgoing) / turn;
+            let tip = from + incoming * along;
+            if turn.abs() > f32::EPSILON && (tip - centre).length() <= limit * half {
+                vec![
+                    Segment::new(from, tip).into_parametric(),
+                    Segment::new(tip, to).into_parametric(),
+                ]
+            } else {
+                vec![Segment::new(from, to).into_parametric()]
+            }
+        }
+    }
+}
+
+/// the cap at the end of `function`, running from its left edge round to its right
+fn end_cap(
+    function: &dyn ParametricFunction2D,
+    half: f32,
+    cap: LineCap,
+) -> Vec<Rc<Box<dyn ParametricFunction2D>>> {
+    let end = function.end();
+    let forward: Vector = function.tangent(T::end()) * half;
+    let left = end + function.normal(T::end()) * half;
+    let right = end - function.normal(T::end()) * half;
+
+    match cap {
+        LineCap::Butt => vec![Segment::new(left, right).into_parametric()],
+        LineCap::Round => vec![arc(end, left, -std::f32::consts::PI)],
+        LineCap::Square => vec![
+            Segment::new(left, left + forward).into_parametric(),
+            Segment::new(left + forward, right + forward).into_parametric(),
+            Segment::new(right + forward, right).into_parametric(),
+        ],
+    }
+}
+
+/// the arc around `centre` starting at `from` and sweeping through `sweep` radians (anticlockwise if positive)
+fn arc(centre: Point, from: Point, sweep: f32) -> Rc<Box<dyn ParametricFunction2D>> {
+    let offset = from - centre;
+    (move |t: T| {
+        let (sin, cos) = (sweep * t.value()).sin_cos();
+        centre
+            + Vector::new(
+                offset.x * cos - offset.y * sin,
+                offset.x * sin + offset.y * cos,
+            )
+    })
+    .into_parametric()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn path(points: &[(f32, f32)]) -> Vec<Rc<Box<dyn ParametricFunction2D>>> {
+        points
+            .windows(2)
+            .map(|w| Segment::new(w[0].into(), w[1].into()).into_parametric())
+            .collect()
+    }
+
+    fn has_vertex(outline: &Concat, point: Point) -> bool {
+        outline
+            .functions
+            .iter()
+            .any(|f| (f.end() - point).length() < 1e-3)
+    }
+
+    #[test]
+    fn test_stroke_outline_caps() {
+        let line = path(&[(0.0, 0.0), (2.0, 0.0)]);
+
+        let outlines = stroke_outline(&line, 2.0, LineCap::Butt, LineJoin::Bevel, 1e-3).unwrap();
+        assert_eq!(outlines.len(), 1);
+        let outline = &outlines[0];
+
+        let res = outline.start();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-3);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-3);
+        let res = outline.end();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-3);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-3);
+        assert!(has_vertex(outline, (2.0, -1.0).into()));
+
+        let outline =
+            &stroke_outline(&line, 2.0, LineCap::Square, LineJoin::Bevel, 1e-3).unwrap()[0];
+        assert!(has_vertex(outline, (3.0, 1.0).into()));
+        assert!(has_vertex(outline, (-1.0, -1.0).into()));
+
+        let outline =
+            &stroke_outline(&line, 2.0, LineCap::Round, LineJoin::Bevel, 1e-3).unwrap()[0];
+        let max_x = outline
+            .linspace(400)
+            .iter()
+            .map(|p| p.x)
+            .fold(f32::MIN, f32::max);
+        assert_relative_eq!(max_x, 3.0, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn test_stroke_outline_joins() {
+        // turns right at (2, 0), so the left edge is on the outside of the corner
+        let corner = path(&[(0.0, 0.0), (2.0, 0.0), (2.0, -2.0)]);
+
+        let outline =
+            &stroke_outline(&corner, 2.0, LineCap::Butt, LineJoin::Miter(4.0), 1e-3).unwrap()[0];
+        assert!(has_vertex(outline, (3.0, 1.0).into()));
+
+        // a miter limit below sqrt(2) rules out the right angle
+        let outline =
+            &stroke_outline(&corner, 2.0, LineCap::Butt, LineJoin::Miter(1.2), 1e-3).unwrap()[0];
+        assert!(!has_vertex(outline, (3.0, 1.0).into()));
+
+        let outline =
+            &stroke_outline(&corner, 2.0, LineCap::Butt, LineJoin::Round, 1e-3).unwrap()[0];
+        let closest = outline.closest_point((3.0, 1.0).into(), 400);
+        assert_relative_eq!(
+            (closest - Point::new(2.0, 0.0)).length(),
+            1.0,
+            epsilon = 1e-2
+        );
+    }
+
+    #[test]
+    fn test_stroke_outline_closed() {
+        let square = Concat::new(path(&[
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+            (0.0, 0.0),
+        ]))
+        .unwrap();
+        let outlines = square
+            .stroke_outline(0.5, LineCap::Butt, LineJoin::Miter(4.0), 1e-3)
+            .unwrap();
+        assert_eq!(outlines.len(), 2);
+
+        // anticlockwise, so the left edge is the inner one and the right edge the outer one
+        assert!(has_vertex(&outlines[1], (-0.25, -0.25).into()));
+        assert!(has_vertex(&outlines[1], (2.25, 2.25).into()));
+
+        assert_eq!(
+            stroke_outline(&[], 1.0, LineCap::Butt, LineJoin::Bevel, 1e-3).err(),
+            Some(ParametricsError::Empty)
+        );
+    }
+}
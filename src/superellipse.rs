@@ -0,0 +1,137 @@
+//! Superellipses (Lamé curves) and Gielis' superformula, which generalises them
+
+use crate::core::{ParametricFunction2D, Point, T};
+
+/// The superellipse `|x / rx|^n + |y / ry|^n = 1` around `centre`, where `n` is the `exponent` - a diamond at 1, an
+/// ellipse at 2 and squarer and squarer towards a rectangle above that (a squircle at 4). It starts on the positive
+/// x-axis and goes round anticlockwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Superellipse {
+    pub centre: Point,
+    pub rx: f32,
+    pub ry: f32,
+    pub exponent: f32,
+}
+
+impl Superellipse {
+    pub fn new(centre: Point, rx: f32, ry: f32, exponent: f32) -> Self {
+        Self {
+            centre,
+            rx,
+            ry,
+            exponent,
+        }
+    }
+}
+
+impl ParametricFunction2D for Superellipse {
+    fn evaluate(&self, t: T) -> Point {
+        let (sin, cos) = (t.value() * std::f32::consts::TAU).sin_cos();
+        let power = 2.0 / self.exponent;
+        (
+            self.centre.x + self.rx * cos.signum() * cos.abs().powf(power),
+            self.centre.y + self.ry * sin.signum() * sin.abs().powf(power),
+        )
+            .into()
+    }
+}
+
+/// The closed curve given by Gielis' superformula around `centre`, whose radius at the angle `phi` (in radians) is
+/// `(|cos(m phi / 4) / a|^n2 + |sin(m phi / 4) / b|^n3)^(-1 / n1)` - `m` sets the rotational symmetry and the
+/// exponents how pinched or puffed out the shape is. It starts on the positive x-axis and goes round anticlockwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Superformula {
+    pub centre: Point,
+    pub a: f32,
+    pub b: f32,
+    pub m: f32,
+    pub n1: f32,
+    pub n2: f32,
+    pub n3: f32,
+}
+
+impl Superformula {
+    pub fn new(centre: Point, a: f32, b: f32, m: f32, n1: f32, n2: f32, n3: f32) -> Self {
+        Self {
+            centre,
+            a,
+            b,
+            m,
+            n1,
+            n2,
+            n3,
+        }
+    }
+
+    /// returns the radius at `phi` radians - zero where the formula blows up
+    pub fn radius(&self, phi: f32) -> f32 {
+        let (sin, cos) = (self.m * phi / 4.0).sin_cos();
+        let sum = (cos / self.a).abs().powf(self.n2) + (sin / self.b).abs().powf(self.n3);
+        let r = sum.powf(-1.0 / self.n1);
+        if r.is_finite() {
+            r
+        } else {
+            0.0
+        }
+    }
+}
+
+impl ParametricFunction2D for Superformula {
+    fn evaluate(&self, t: T) -> Point {
+        let phi = t.value() * std::f32::consts::TAU;
+        let r = self.radius(phi);
+        let (sin, cos) = phi.sin_cos();
+        (self.centre.x + r * cos, self.centre.y + r * sin).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_superellipse() {
+        let s = Superellipse::new((1.0, 1.0).into(), 2.0, 1.0, 4.0);
+
+        let res = s.start();
+        assert_relative_eq!(res.x, 3.0);
+        assert_relative_eq!(res.y, 1.0);
+
+        let res = s.evaluate(T::new(0.25));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-3);
+        assert_relative_eq!(res.y, 2.0, epsilon = 1e-6);
+
+        // every point is on the curve
+        for p in s.linspace(20) {
+            let x = ((p.x - 1.0) / 2.0).abs();
+            let y = (p.y - 1.0).abs();
+            assert_relative_eq!(x.powi(4) + y.powi(4), 1.0, epsilon = 1e-5);
+        }
+
+        // a diamond at 1
+        let s = Superellipse::new((0.0, 0.0).into(), 1.0, 1.0, 1.0);
+        let res = s.evaluate(T::new(0.125));
+        assert_relative_eq!(res.x, 0.5, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.5, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_superformula() {
+        // all the exponents at 2 give a circle
+        let s = Superformula::new((1.0, 1.0).into(), 1.0, 1.0, 4.0, 2.0, 2.0, 2.0);
+        for p in s.linspace(20) {
+            assert_relative_eq!((p - Point::new(1.0, 1.0)).length(), 1.0, epsilon = 1e-5);
+        }
+
+        // and all at 1 a square standing on its corner
+        let s = Superformula::new((0.0, 0.0).into(), 1.0, 1.0, 4.0, 1.0, 1.0, 1.0);
+        let res = s.start();
+        assert_relative_eq!(res.x, 1.0);
+        assert_relative_eq!(res.y, 0.0);
+        let res = s.evaluate(T::new(0.125));
+        assert_relative_eq!(res.x, 0.5, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.5, epsilon = 1e-6);
+        assert_relative_eq!(s.radius(std::f32::consts::FRAC_PI_2), 1.0, epsilon = 1e-6);
+    }
+}
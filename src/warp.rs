@@ -0,0 +1,109 @@
+//! Bending curves along other curves
+
+use std::rc::Rc;
+
+use crate::{
+    arclength::ArcLengthTable,
+    core::{optimize_all, IntoParametric2D, ParametricFunction2D, Point, T},
+};
+
+/// A `profile` bent along a `rail` - the x coordinate of each point on the profile becomes a distance along the rail
+/// and the y coordinate a displacement along the rail's [`ParametricFunction2D::normal`]. So a profile running along
+/// the x-axis follows the rail exactly, and a sine wave along the x-axis becomes a wave along the rail. Parts of the
+/// profile with x outside `0.0..=length` of the rail carry straight on from its ends.
+#[derive(Clone)]
+pub struct WarpAlong {
+    pub profile: Rc<Box<dyn ParametricFunction2D>>,
+    pub rail: Rc<Box<dyn ParametricFunction2D>>,
+    table: ArcLengthTable,
+}
+
+impl WarpAlong {
+    /// see [`ArcLengthTable::new`] for the meaning of `tolerance`
+    pub fn new(
+        profile: impl IntoParametric2D,
+        rail: impl IntoParametric2D,
+        tolerance: f32,
+    ) -> Self {
+        let rail = rail.into_parametric();
+        let table = ArcLengthTable::new(rail.as_ref().as_ref(), tolerance);
+        Self {
+            profile: profile.into_parametric(),
+            rail,
+            table,
+        }
+    }
+}
+
+impl ParametricFunction2D for WarpAlong {
+    fn evaluate(&self, t: T) -> Point {
+        let p = self.profile.evaluate(t);
+
+        let rail_t = self.table.t_at_length(p.x);
+        let overshoot = if p.x < 0.0 {
+            p.x
+        } else {
+            (p.x - self.table.total()).max(0.0)
+        };
+
+        self.rail.evaluate(rail_t)
+            + self.rail.tangent(rail_t) * overshoot
+            + self.rail.normal(rail_t) * p.y
+    }
+
+    fn optimize(&self) -> Option<Rc<Box<dyn ParametricFunction2D>>> {
+        let [profile, rail] = optimize_all(&[self.profile.clone(), self.rail.clone()])?
+            .try_into()
+            .ok()?;
+        Some(Rc::new(Box::new(WarpAlong {
+            profile,
+            rail,
+            table: self.table.clone(),
+        })))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "WarpAlong({}, {})",
+            self.profile.describe(),
+            self.rail.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{circle::Circle, segment::Segment};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_warp_along() {
+        let rail = Segment::new((1.0, 1.0).into(), (1.0, 5.0).into());
+        let profile = Segment::new((0.0, 0.0).into(), (6.0, 1.0).into());
+        let w = WarpAlong::new(profile, rail, 1e-4);
+
+        // the rail runs up the y-axis, so its normal points towards -x
+        let res = w.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 0.5, epsilon = 1e-4);
+        assert_relative_eq!(res.y, 4.0, epsilon = 1e-4);
+
+        // beyond the end of the rail
+        let res = w.end();
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(res.y, 7.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_warp_along_circle() {
+        let rail = Circle::new((0.0, 0.0).into(), 2.0, None);
+        let length = std::f32::consts::TAU * 2.0;
+        let profile = Segment::new((0.0, -1.0).into(), (length, -1.0).into());
+        let w = WarpAlong::new(profile, rail, 1e-4);
+
+        // offsetting to the right of an anticlockwise circle moves outwards
+        for p in w.linspace(10) {
+            assert_relative_eq!(p.to_vector().length(), 3.0, epsilon = 1e-2);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Smooth and piecewise linear waves running along a straight baseline
+
+use crate::core::{ParametricFunction2D, Point, Vector, T};
+
+/// A sine wave running along the baseline from `start` to `end` through `cycles` cycles, swinging `amplitude` either
+/// side of it (positive to the left first). `phase` shifts the wave along, in cycles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SineWave {
+    pub start: Point,
+    pub end: Point,
+    pub amplitude: f32,
+    pub cycles: f32,
+    pub phase: f32,
+}
+
+impl SineWave {
+    pub fn new(start: Point, end: Point, amplitude: f32, cycles: f32, phase: f32) -> Self {
+        Self {
+            start,
+            end,
+            amplitude,
+            cycles,
+            phase,
+        }
+    }
+}
+
+impl ParametricFunction2D for SineWave {
+    fn evaluate(&self, t: T) -> Point {
+        let offset =
+            self.amplitude * ((self.cycles * t.value() + self.phase) * std::f32::consts::TAU).sin();
+        self.start.lerp(self.end, t.value()) + baseline_normal(self.start, self.end) * offset
+    }
+}
+
+/// The shape of each cycle of a [`ZigZag`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZigZagKind {
+    /// straight up to the crest, down to the trough and back
+    Triangle,
+    /// a steady climb from trough to crest with a sheer drop back down
+    Sawtooth,
+    /// flat along the crest and then the trough, with sheer sides
+    Square,
+}
+
+/// A piecewise linear wave running along the baseline from `start` to `end` through `cycles` cycles, swinging
+/// `amplitude` either side of it (positive to the left first) in the shape given by `kind`. Each cycle starts and
+/// ends on the baseline, and the wave is traversed at a constant speed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZigZag {
+    pub start: Point,
+    pub end: Point,
+    pub amplitude: f32,
+    pub cycles: f32,
+    pub kind: ZigZagKind,
+}
+
+impl ZigZag {
+    /// a triangle wave
+    pub fn new(start: Point, end: Point, amplitude: f32, cycles: f32) -> Self {
+        Self {
+            start,
+            end,
+            amplitude,
+            cycles,
+            kind: ZigZagKind::Triangle,
+        }
+    }
+
+    /// a sawtooth wave
+    pub fn sawtooth(start: Point, end: Point, amplitude: f32, cycles: f32) -> Self {
+        Self {
+            kind: ZigZagKind::Sawtooth,
+            ..Self::new(start, end, amplitude, cycles)
+        }
+    }
+
+    /// a square wave
+    pub fn square(start: Point, end: Point, amplitude: f32, cycles: f32) -> Self {
+        Self {
+            kind: ZigZagKind::Square,
+            ..Self::new(start, end, amplitude, cycles)
+        }
+    }
+
+    /// returns the corners of the wave in order, from `start` to the end of the last (possibly partial) cycle
+    pub fn corners(&self) -> Vec<Point> {
+        if self.cycles <= 0.0 {
+            return vec![self.start, self.end];
+        }
+
+        let whole = self.cycles.floor();
+        let mut corners = vec![self.start];
+        for k in 0..whole as usize {
+            corners.extend(
+                self.cycle_lines(k as f32, 1.0)
+                    .map(|(_, to)| self.to_world(to)),
+            );
+        }
+        corners.extend(
+            self.cycle_lines(whole, self.cycles - whole)
+                .map(|(_, to)| self.to_world(to)),
+        );
+        corners
+    }
+
+    /// returns the lines of the cycle starting `k` cycles along, as pairs of (cycles along, amplitudes across) - cut
+    /// short `limit` cycles into it, to end a partial last cycle
+    fn cycle_lines(&self, k: f32, limit: f32) -> impl Iterator<Item = ((f32, f32), (f32, f32))> {
+        let cycle: &[(f32, f32)] = match self.kind {
+            ZigZagKind::Triangle => &[(0.0, 0.0), (0.25, 1.0), (0.75, -1.0), (1.0, 0.0)],
+            ZigZagKind::Sawtooth => &[(0.0, 0.0), (0.5, 1.0), (0.5, -1.0), (1.0, 0.0)],
+            ZigZagKind::Square => &[
+                (0.0, 0.0),
+                (0.0, 1.0),
+                (0.5, 1.0),
+                (0.5, -1.0),
+                (1.0, -1.0),
+                (1.0, 0.0),
+            ],
+        };
+
+        cycle
+            .windows(2)
+            .filter(move |w| limit > 0.0 && (w[0].0 < limit || w[1].0 <= limit))
+            .map(move |w| {
+                let (from, mut to) = (w[0], w[1]);
+                if to.0 > limit {
+                    let s = (limit - from.0) / (to.0 - from.0);
+                    to = (limit, from.1 + (to.1 - from.1) * s);
+                }
+                ((from.0 + k, from.1), (to.0 + k, to.1))
+            })
+    }
+
+    /// returns the point `u` cycles along the baseline and `v` amplitudes across it
+    fn to_world(&self, (u, v): (f32, f32)) -> Point {
+        let normal = baseline_normal(self.start, self.end);
+        self.start.lerp(self.end, u / self.cycles) + normal * v * self.amplitude
+    }
+
+    /// returns the total length of the lines of a cycle from [`ZigZag::cycle_lines`]
+    fn cycle_length(&self, k: f32, limit: f32) -> f32 {
+        self.cycle_lines(k, limit)
+            .map(|(from, to)| (self.to_world(to) - self.to_world(from)).length())
+            .sum()
+    }
+}
+
+impl ParametricFunction2D for ZigZag {
+    fn evaluate(&self, t: T) -> Point {
+        if self.cycles <= 0.0 {
+            return self.start.lerp(self.end, t.value());
+        }
+
+        // every whole cycle is the same length, so the one `t` falls in can be found without walking the others
+        let whole = self.cycles.floor();
+        let partial = self.cycles - whole;
+        let length = self.cycle_length(0.0, 1.0);
+        let total = length * whole + self.cycle_length(whole, partial);
+        if total <= 0.0 {
+            return self.start;
+        }
+
+        let target = t.value() * total;
+        let k = if length > 0.0 {
+            (target / length).floor().min(whole)
+        } else {
+            whole
+        };
+        let limit = if k < whole { 1.0 } else { partial };
+
+        let mut remaining = target - k * length;
+        let mut last = self.to_world((k, 0.0));
+        for (from, to) in self.cycle_lines(k, limit) {
+            let (from, to) = (self.to_world(from), self.to_world(to));
+            let length = (to - from).length();
+            if remaining <= length && length > 0.0 {
+                return from.lerp(to, remaining / length);
+            }
+            remaining -= length;
+            last = to;
+        }
+        last
+    }
+}
+
+/// the unit vector a quarter turn anticlockwise from the baseline from `start` to `end` - zero if they coincide
+fn baseline_normal(start: Point, end: Point) -> Vector {
+    let direction = (end - start).try_normalize().unwrap_or_else(Vector::zero);
+    Vector::new(-direction.y, direction.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_sine_wave() {
+        let w = SineWave::new((0.0, 0.0).into(), (4.0, 0.0).into(), 0.5, 2.0, 0.0);
+
+        let res = w.start();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        let res = w.evaluate(T::new(0.125));
+        assert_relative_eq!(res.x, 0.5);
+        assert_relative_eq!(res.y, 0.5);
+
+        let res = w.evaluate(T::new(0.375));
+        assert_relative_eq!(res.x, 1.5);
+        assert_relative_eq!(res.y, -0.5, epsilon = 1e-6);
+
+        let res = w.end();
+        assert_relative_eq!(res.x, 4.0);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+
+        // a quarter cycle of phase starts at the crest, and the wave follows the baseline round
+        let w = SineWave::new((1.0, 1.0).into(), (1.0, 3.0).into(), 1.0, 1.0, 0.25);
+        let res = w.start();
+        assert_relative_eq!(res.x, 0.0);
+        assert_relative_eq!(res.y, 1.0);
+    }
+
+    #[test]
+    fn test_zigzag() {
+        let z = ZigZag::new((0.0, 0.0).into(), (4.0, 0.0).into(), 1.0, 2.0);
+        assert_eq!(z.corners().len(), 7);
+
+        let res = z.evaluate(T::new(0.125));
+        assert_relative_eq!(res.x, 0.5, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+
+        let res = z.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 2.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+
+        let res = z.end();
+        assert_relative_eq!(res.x, 4.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        // half a cycle stops at the baseline on the way down
+        let z = ZigZag::new((0.0, 0.0).into(), (2.0, 0.0).into(), 1.0, 0.5);
+        let res = z.end();
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 0.0);
+    }
+
+    #[test]
+    fn test_sawtooth_and_square() {
+        let z = ZigZag::sawtooth((0.0, 0.0).into(), (2.0, 0.0).into(), 1.0, 1.0);
+        let corners = z.corners();
+        assert_eq!(corners.len(), 4);
+        assert_relative_eq!(corners[1].x, 1.0);
+        assert_relative_eq!(corners[1].y, 1.0);
+        assert_relative_eq!(corners[2].x, 1.0);
+        assert_relative_eq!(corners[2].y, -1.0);
+
+        // the sheer sides take their share of the length too
+        let z = ZigZag::square((0.0, 0.0).into(), (2.0, 0.0).into(), 1.0, 1.0);
+        let res = z.evaluate(T::new(0.5));
+        assert_relative_eq!(res.x, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 0.0, epsilon = 1e-6);
+        let res = z.evaluate(T::new(1.0 / 6.0));
+        assert_relative_eq!(res.x, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.y, 1.0, epsilon = 1e-6);
+        let res = z.end();
+        assert_relative_eq!(res.x, 2.0);
+        assert_relative_eq!(res.y, 0.0);
+
+        // finding the cycle directly agrees with walking all the corners, partial cycles included
+        for z in [
+            ZigZag::new((0.0, 0.0).into(), (3.0, 1.0).into(), 0.5, 2.3),
+            ZigZag::sawtooth((0.0, 0.0).into(), (3.0, 1.0).into(), 0.5, 2.5),
+            ZigZag::square((0.0, 0.0).into(), (3.0, 1.0).into(), 0.5, 1.75),
+        ] {
+            let corners = z.corners();
+            for t in T::range(40) {
+                let res = z.evaluate(t);
+                let expected = crate::polyline::along(&corners, true, t);
+                assert_relative_eq!(res.x, expected.x, epsilon = 1e-5);
+                assert_relative_eq!(res.y, expected.y, epsilon = 1e-5);
+            }
+        }
+    }
+}